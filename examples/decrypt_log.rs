@@ -0,0 +1,39 @@
+//! Decrypts a log file written through an `EncryptedWriter` and prints it to stdout.
+//!
+//! Usage: `cargo run --example decrypt_log --features encryption -- <key as 64 hex digits> <file>`
+
+use simplelog::decrypt_log;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::process;
+
+fn parse_key(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut key = [0u8; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(key)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        eprintln!("Usage: {} <key as 64 hex digits> <file>", args[0]);
+        process::exit(2);
+    }
+    let key = parse_key(&args[1]).unwrap_or_else(|| {
+        eprintln!("The key needs to consist of 64 hex digits");
+        process::exit(2);
+    });
+
+    let result = File::open(&args[2])
+        .and_then(|file| decrypt_log(BufReader::new(file), &key, io::stdout().lock()));
+    if let Err(err) = result {
+        eprintln!("Failed to decrypt {}: {}", args[2], err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing background threads, that are stopped by `shutdown`
+
+use std::io;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+
+/// Function stopping a thread and the thread to wait for, until it is stopped
+type Running = Option<(Box<dyn FnOnce() + Send>, JoinHandle<()>)>;
+
+/// Background threads, that were not stopped yet
+static THREADS: Mutex<Vec<BackgroundThread>> = Mutex::new(Vec::new());
+
+/// A background thread of a logger, stopped and joined by `shutdown`
+#[derive(Clone)]
+pub(crate) struct BackgroundThread {
+    inner: Arc<Mutex<Running>>,
+}
+
+impl BackgroundThread {
+    /// Spawns a thread running `f`, which needs to return once `stop` was called
+    pub(crate) fn spawn<F, S>(name: &str, f: F, stop: S) -> io::Result<BackgroundThread>
+    where
+        F: FnOnce() + Send + 'static,
+        S: FnOnce() + Send + 'static,
+    {
+        let handle = thread::Builder::new().name(name.into()).spawn(f)?;
+        let thread = BackgroundThread {
+            inner: Arc::new(Mutex::new(Some((Box::new(stop), handle)))),
+        };
+        lock(&THREADS).push(thread.clone());
+        Ok(thread)
+    }
+
+    /// Stops the thread and waits for it to exit
+    pub(crate) fn stop(&self) {
+        lock(&THREADS).retain(|thread| !Arc::ptr_eq(&thread.inner, &self.inner));
+        let thread = lock(&self.inner).take();
+        if let Some((stop, handle)) = thread {
+            stop();
+            // the thread cannot wait for itself, e.g. if a record it logs ends up calling `shutdown`
+            if handle.thread().id() != thread::current().id() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    /// Returns if the thread was stopped
+    #[cfg(test)]
+    pub(crate) fn is_stopped(&self) -> bool {
+        lock(&self.inner).is_none()
+    }
+}
+
+/// Stops all background threads and waits for them to exit
+pub(crate) fn stop_all() {
+    let threads = std::mem::take(&mut *lock(&THREADS));
+    for thread in threads {
+        thread.stop();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
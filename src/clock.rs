@@ -0,0 +1,125 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the clocks used for the time of records
+
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// Source of the time logged with every record, set through [`ConfigBuilder::set_clock`](crate::ConfigBuilder::set_clock)
+///
+/// The clock is asked once per record and used by all time formats except
+/// `set_time_format_uptime` and `set_time_format_delta`, which measure elapsed time instead.
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the current time
+    fn now(&self) -> SystemTime;
+}
+
+/// The clock of the system, used by default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock always returning the same time, e.g. for snapshot tests of the log output
+///
+/// # Examples
+///
+/// ```
+/// # use simplelog::*;
+/// # use std::time::{Duration, SystemTime};
+/// let config = ConfigBuilder::new()
+///     .set_clock(FixedClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(86400)))
+///     .set_time_format_rfc3339()
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(SystemTime);
+
+impl FixedClock {
+    /// Creates a clock always returning `time`
+    pub fn new(time: SystemTime) -> FixedClock {
+        FixedClock(time)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// A clock starting at a fixed time, that moves on by a fixed step every time it is asked
+///
+/// The first record is logged with the start time, the second one with the start time plus one step and so on.
+#[derive(Debug)]
+pub struct SteppingClock {
+    start: SystemTime,
+    step: Duration,
+    steps: AtomicU32,
+}
+
+impl SteppingClock {
+    /// Creates a clock starting at `start` and advancing by `step`
+    pub fn new(start: SystemTime, step: Duration) -> SteppingClock {
+        SteppingClock {
+            start,
+            step,
+            steps: AtomicU32::new(0),
+        }
+    }
+}
+
+impl Clock for SteppingClock {
+    fn now(&self) -> SystemTime {
+        self.start + self.step * self.steps.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loggers::logging::try_log;
+    use crate::ConfigBuilder;
+    use log::{Level, Record};
+    use std::time::Duration;
+
+    #[test]
+    fn clock() {
+        use crate::SteppingClock;
+        use std::time::SystemTime;
+
+        let config = ConfigBuilder::new()
+            .set_clock(SteppingClock::new(
+                SystemTime::UNIX_EPOCH + Duration::from_secs(86400),
+                Duration::from_millis(1500),
+            ))
+            .set_time_format_rfc3339()
+            .set_time_offset(time::UtcOffset::UTC)
+            .build();
+        let mut out = Vec::new();
+        for _ in 0..2 {
+            try_log(
+                &config,
+                &Record::builder()
+                    .level(Level::Info)
+                    .args(format_args!("msg"))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1970-01-02T00:00:00Z [INFO] msg\n1970-01-02T00:00:01.5Z [INFO] msg\n"
+        );
+    }
+}
@@ -1,11 +1,24 @@
-#[cfg(feature = "termcolor")]
-use log::Level;
-use log::LevelFilter;
+use crate::clock::{Clock, SystemClock};
+use crate::LogRecordOwned;
+use log::{Level, LevelFilter, Metadata};
 
 use std::borrow::Cow;
+use std::fmt;
+use std::io::{Error, Write};
+#[cfg(feature = "termcolor")]
+use std::ops::Range;
+#[cfg(feature = "local-offset")]
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 #[cfg(feature = "termcolor")]
-use termcolor::Color;
-pub use time::{format_description::FormatItem, macros::format_description, UtcOffset};
+use termcolor::{Color, ColorSpec};
+use time::format_description::OwnedFormatItem;
+pub use time::{
+    error::InvalidFormatDescription, format_description::FormatItem, macros::format_description,
+    UtcOffset,
+};
 
 #[derive(Debug, Clone, Copy)]
 /// Padding to be used for logging the level
@@ -40,6 +53,28 @@ pub enum TargetPadding {
     Off,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Truncation of targets exceeding the width of the `TargetPadding`
+pub enum TargetTruncation {
+    /// Let long targets exceed the padding width
+    Off,
+    /// Cut off the start of long targets, replacing it with the given ellipsis
+    Start(&'static str),
+    /// Cut off the end of long targets, replacing it with the given ellipsis
+    End(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Abbreviation of long targets
+pub enum TargetAbbreviation {
+    /// Print the full target
+    Off,
+    /// Keep only the last usize many `::` separated segments, e.g. `network::my_mod`
+    LastSegments(usize),
+    /// Shorten all segments but the last one to their first character, e.g. `m::n::my_mod`
+    FirstLetters,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 /// Mode for logging the thread name or id or both.
 pub enum ThreadLogMode {
@@ -51,14 +86,502 @@ pub enum ThreadLogMode {
     Both,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Handling of messages spanning multiple lines
+pub enum MultiLineMode {
+    /// Print the continuation lines as they are
+    Off,
+    /// Indent the continuation lines to align with the start of the message
+    Indent,
+    /// Repeat the time, level, etc. in front of every continuation line
+    RepeatHeader,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Alignment of the messages, by padding the header (time, level, thread, target, ...) in front of them
+pub enum HeaderAlignment {
+    /// Print the message right after the header
+    Off,
+    /// Pad the header to at least the given width in characters
+    Fixed(usize),
+    /// Pad the header to the width of the widest header logged so far
+    Auto,
+}
+
+/// Attention signal of the `TermLogger` for error records, see `ConfigBuilder::set_error_attention`
+///
+/// # Examples
+///
+/// ```
+/// # use simplelog::{ConfigBuilder, ErrorAttention};
+/// let config = ConfigBuilder::new()
+///     .set_error_attention(ErrorAttention::new().title("my_tool"))
+///     .build();
+/// ```
+#[cfg(feature = "console-attention")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ErrorAttention {
+    pub(crate) title: Option<String>,
+}
+
+#[cfg(feature = "console-attention")]
+impl ErrorAttention {
+    /// Creates an attention signal doing nothing
+    pub fn new() -> ErrorAttention {
+        ErrorAttention::default()
+    }
+
+    /// Sets the console title to `title` followed by the number of errors logged so far,
+    /// e.g. `my_tool (3 errors)`
+    ///
+    /// The title is set through the OSC 0 escape sequence, supported by most terminal emulators
+    /// (including the Windows Terminal).
+    pub fn title<S: Into<String>>(mut self, title: S) -> ErrorAttention {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// Text style used for printing parts of a log line (if the logger supports it)
+///
+/// # Examples
+///
+/// ```
+/// # use simplelog::{Color, ConfigBuilder, Level, Style};
+/// let config = ConfigBuilder::new()
+///     .set_level_style(Level::Error, Style::new().bold().fg(Color::Red))
+///     .build();
+/// ```
+#[cfg(feature = "termcolor")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub(crate) fg: Option<Color>,
+    pub(crate) bg: Option<Color>,
+    pub(crate) bold: bool,
+    pub(crate) italic: bool,
+    pub(crate) underline: bool,
+    pub(crate) intense: bool,
+    pub(crate) dimmed: bool,
+}
+
+#[cfg(feature = "termcolor")]
+impl Style {
+    /// Create a new style using the default colors without any effects
+    pub const fn new() -> Style {
+        Style {
+            fg: None,
+            bg: None,
+            bold: false,
+            italic: false,
+            underline: false,
+            intense: false,
+            dimmed: false,
+        }
+    }
+
+    /// Set the foreground color
+    pub const fn fg(mut self, color: Color) -> Style {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Set the background color
+    pub const fn bg(mut self, color: Color) -> Style {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Print the text in bold
+    pub const fn bold(mut self) -> Style {
+        self.bold = true;
+        self
+    }
+
+    /// Print the text in italics
+    pub const fn italic(mut self) -> Style {
+        self.italic = true;
+        self
+    }
+
+    /// Underline the text
+    pub const fn underline(mut self) -> Style {
+        self.underline = true;
+        self
+    }
+
+    /// Use the intense variants of the colors
+    pub const fn intense(mut self) -> Style {
+        self.intense = true;
+        self
+    }
+
+    /// Print the text dimmed
+    pub const fn dimmed(mut self) -> Style {
+        self.dimmed = true;
+        self
+    }
+
+    pub(crate) fn to_color_spec(self) -> ColorSpec {
+        let mut spec = ColorSpec::new();
+        spec.set_fg(self.fg)
+            .set_bg(self.bg)
+            .set_bold(self.bold)
+            .set_italic(self.italic)
+            .set_underline(self.underline)
+            .set_intense(self.intense)
+            .set_dimmed(self.dimmed);
+        spec
+    }
+}
+
+#[cfg(feature = "termcolor")]
+impl From<Color> for Style {
+    fn from(color: Color) -> Style {
+        Style::new().fg(color)
+    }
+}
+
+/// Built-in palettes for the level styles, applied via [`ConfigBuilder::set_theme`]
+///
+/// # Examples
+///
+/// ```
+/// # use simplelog::{ConfigBuilder, Theme};
+/// let config = ConfigBuilder::new()
+///     .set_theme(Theme::Solarized)
+///     .build();
+/// ```
+#[cfg(feature = "termcolor")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Theme {
+    /// The colors used by [`Config::default`]
+    Default,
+    /// Accent colors of the solarized palette (requires a truecolor terminal)
+    Solarized,
+    /// No colors, errors and warnings are printed in bold and debug/trace dimmed
+    Monochrome,
+    /// Bold, intense colors with inverted errors for low-contrast displays
+    HighContrast,
+}
+
+#[cfg(feature = "termcolor")]
+impl Theme {
+    /// Level styles of this theme, indexed by `Level as usize` (index 0 is unused)
+    pub const fn level_styles(self) -> [Style; 6] {
+        match self {
+            Theme::Default => [
+                Style::new(),
+                Style::new().fg(Color::Red),
+                Style::new().fg(Color::Yellow),
+                Style::new().fg(Color::Blue),
+                Style::new().fg(Color::Cyan),
+                Style::new().fg(Color::White),
+            ],
+            Theme::Solarized => [
+                Style::new(),
+                Style::new().fg(Color::Rgb(220, 50, 47)),
+                Style::new().fg(Color::Rgb(181, 137, 0)),
+                Style::new().fg(Color::Rgb(38, 139, 210)),
+                Style::new().fg(Color::Rgb(42, 161, 152)),
+                Style::new().fg(Color::Rgb(147, 161, 161)),
+            ],
+            Theme::Monochrome => [
+                Style::new(),
+                Style::new().bold(),
+                Style::new().bold(),
+                Style::new(),
+                Style::new().dimmed(),
+                Style::new().dimmed(),
+            ],
+            Theme::HighContrast => [
+                Style::new(),
+                Style::new()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .bold()
+                    .intense(),
+                Style::new().fg(Color::Yellow).bold().intense(),
+                Style::new().fg(Color::Green).bold().intense(),
+                Style::new().fg(Color::Cyan).intense(),
+                Style::new().fg(Color::White).intense(),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Precision of unix timestamps
+pub enum UnixPrecision {
+    /// Seconds since the unix epoch
+    Seconds,
+    /// Milliseconds since the unix epoch
+    Millis,
+    /// Microseconds since the unix epoch
+    Micros,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Counter used for the sequence numbers of records
+pub enum SequenceMode {
+    /// Every logger numbers its records separately
+    PerLogger,
+    /// All loggers share one counter for the whole process
+    Global,
+}
+
+static GLOBAL_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// State kept by every logger on its own, even if the loggers were built from clones of one `Config`.
+///
+/// Cloning starts over from the default, so loggers don't share counters, header widths or clocks.
+#[derive(Debug, Default)]
+pub(crate) struct PerLogger<T>(T);
+
+impl<T: Default> Clone for PerLogger<T> {
+    fn clone(&self) -> Self {
+        PerLogger::default()
+    }
+}
+
+impl<T> std::ops::Deref for PerLogger<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Sequence counter of a single logger
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SequenceCounter(PerLogger<AtomicU64>);
+
+/// Number of records written by a logger, see `SharedLogger::records_written`
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RecordCounter(PerLogger<AtomicU64>);
+
+/// Width of the widest header of a logger, for `HeaderAlignment::Auto`
+#[derive(Debug, Default, Clone)]
+pub(crate) struct HeaderWidth(PerLogger<AtomicUsize>);
+
+/// Remembers when the previous record was logged, for `TimeFormat::Delta`
+#[derive(Debug, Default, Clone)]
+pub(crate) struct DeltaClock(PerLogger<AtomicU64>);
+
+impl DeltaClock {
+    /// Returns the time elapsed since the previous call (or the initialization of logging)
+    pub(crate) fn tick(&self) -> Duration {
+        let now = crate::loggers::logging::start_time().elapsed().as_nanos() as u64;
+        let previous = self.0.swap(now, Ordering::Relaxed);
+        Duration::from_nanos(now.saturating_sub(previous))
+    }
+}
+
+/// Seconds after which the local offset is resolved again
+#[cfg(feature = "local-offset")]
+const LOCAL_OFFSET_REFRESH: u64 = 60;
+
+/// The local offset, resolved again periodically while logging
+#[cfg(feature = "local-offset")]
+#[derive(Debug)]
+pub(crate) struct LocalOffset {
+    /// The offset in seconds
+    offset: AtomicI32,
+    /// When the offset was last resolved, in seconds since the initialization of logging
+    resolved: AtomicU64,
+}
+
+#[cfg(feature = "local-offset")]
+impl LocalOffset {
+    fn new(fallback: UtcOffset) -> LocalOffset {
+        let offset = UtcOffset::current_local_offset().unwrap_or(fallback);
+        LocalOffset {
+            offset: AtomicI32::new(offset.whole_seconds()),
+            resolved: AtomicU64::new(crate::loggers::logging::start_time().elapsed().as_secs()),
+        }
+    }
+
+    /// Returns the local offset, resolving it again if it is outdated.
+    ///
+    /// If the offset cannot be determined soundly, the previous one is kept.
+    fn get(&self) -> UtcOffset {
+        let now = crate::loggers::logging::start_time().elapsed().as_secs();
+        let resolved = self.resolved.load(Ordering::Relaxed);
+        if now.saturating_sub(resolved) >= LOCAL_OFFSET_REFRESH
+            && self
+                .resolved
+                .compare_exchange(resolved, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            if let Ok(offset) = UtcOffset::current_local_offset() {
+                self.offset.store(offset.whole_seconds(), Ordering::Relaxed);
+            }
+        }
+        UtcOffset::from_whole_seconds(self.offset.load(Ordering::Relaxed)).unwrap_or(UtcOffset::UTC)
+    }
+}
+
+#[cfg(feature = "local-offset")]
+impl Clone for LocalOffset {
+    fn clone(&self) -> Self {
+        LocalOffset {
+            offset: AtomicI32::new(self.offset.load(Ordering::Relaxed)),
+            resolved: AtomicU64::new(self.resolved.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// The formatted time, reused until the time moves on to the next interval of the granularity
+#[derive(Debug)]
+pub(crate) struct TimeCache {
+    granularity: Duration,
+    /// The interval since the unix epoch the time was formatted in and the formatted time
+    formatted: Mutex<(Option<u128>, Vec<u8>)>,
+}
+
+impl TimeCache {
+    fn new(granularity: Duration) -> TimeCache {
+        TimeCache {
+            granularity,
+            formatted: Mutex::new((None, Vec::new())),
+        }
+    }
+
+    /// Writes the cached time, calling `format` to format it again if `now` is in another interval
+    pub(crate) fn write<W, F>(&self, write: &mut W, now: SystemTime, format: F) -> Result<(), Error>
+    where
+        W: Write,
+        F: FnOnce(&mut Vec<u8>) -> Result<(), Error>,
+    {
+        let now = now
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        let interval = now.as_nanos() / self.granularity.as_nanos();
+
+        let mut formatted = self.formatted.lock().unwrap();
+        let (formatted_interval, time) = &mut *formatted;
+        if *formatted_interval != Some(interval) {
+            time.clear();
+            format(time)?;
+            *formatted_interval = Some(interval);
+        }
+        write.write_all(time)
+    }
+}
+
+impl Clone for TimeCache {
+    fn clone(&self) -> Self {
+        TimeCache::new(self.granularity)
+    }
+}
+
+/// Predicate deciding which records are logged, set by [`ConfigBuilder::set_filter`]
+#[derive(Clone)]
+pub(crate) struct RecordFilter(pub(crate) Arc<dyn Fn(&Metadata<'_>) -> bool + Send + Sync>);
+
+impl fmt::Debug for RecordFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RecordFilter")
+    }
+}
+
+/// Part of the messages printed in a different style, added by [`ConfigBuilder::add_highlight`]
+#[cfg(feature = "termcolor")]
+#[derive(Debug, Clone)]
+pub(crate) enum Highlight {
+    Text(String),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+#[cfg(feature = "termcolor")]
+impl Highlight {
+    /// Returns the range of the first non-empty match starting at or after `start`
+    pub(crate) fn find_at(&self, text: &str, start: usize) -> Option<Range<usize>> {
+        match self {
+            Highlight::Text(pattern) if pattern.is_empty() => None,
+            Highlight::Text(pattern) => text[start..]
+                .find(pattern.as_str())
+                .map(|i| start + i..start + i + pattern.len()),
+            #[cfg(feature = "regex")]
+            Highlight::Regex(regex) => regex
+                .find_iter(&text[start..])
+                .find(|m| !m.is_empty())
+                .map(|m| start + m.start()..start + m.end()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum TimeFormat {
+    Unix(UnixPrecision),
+    Uptime(usize),
+    Delta(DeltaClock),
     Rfc2822,
     Rfc3339,
     Custom(&'static [time::format_description::FormatItem<'static>]),
+    Owned(OwnedFormatItem),
+    #[cfg(feature = "chrono")]
+    Chrono {
+        format: String,
+        local: bool,
+        /// If the format string can be parsed, checked once in the setter
+        valid: bool,
+    },
+}
+
+impl TimeFormat {
+    /// Returns if the format represents a point in time, which can be cached
+    pub(crate) fn is_calendar(&self) -> bool {
+        !matches!(
+            self,
+            TimeFormat::Uptime(_) | TimeFormat::Delta(_) | TimeFormat::Unix(_)
+        )
+    }
+}
+
+/// Error returned by [`ConfigBuilder::try_build`] for invalid configurations
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The time format cannot be used to format a time
+    InvalidTimeFormat(time::error::Format),
+    /// The time format is empty, use `set_time_level(LevelFilter::Off)` to disable the time instead
+    EmptyTimeFormat,
+    /// Targets should be truncated, but there is no target padding to take the width from
+    TruncationWithoutPadding,
+    /// The chrono time format string is invalid
+    #[cfg(feature = "chrono")]
+    InvalidChronoFormat(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::InvalidTimeFormat(err) => write!(f, "Invalid time format: {}", err),
+            ConfigError::EmptyTimeFormat => write!(f, "Empty time format"),
+            ConfigError::TruncationWithoutPadding => {
+                write!(f, "Target truncation requires a target padding")
+            }
+            #[cfg(feature = "chrono")]
+            ConfigError::InvalidChronoFormat(format) => {
+                write!(f, "Invalid chrono time format: {}", format)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::InvalidTimeFormat(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 /// UTF-8 end of line character sequences
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LineEnding {
     /// Line feed
     LF,
@@ -78,6 +601,31 @@ pub enum LineEnding {
     PS,
 }
 
+impl LineEnding {
+    /// Returns the native line ending of the platform, `Crlf` on Windows and `LF` elsewhere
+    pub fn platform() -> LineEnding {
+        if cfg!(windows) {
+            LineEnding::Crlf
+        } else {
+            LineEnding::LF
+        }
+    }
+
+    /// Returns the character sequence of the line ending
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::LF => "\u{000A}",
+            LineEnding::CR => "\u{000D}",
+            LineEnding::Crlf => "\u{000D}\u{000A}",
+            LineEnding::VT => "\u{000B}",
+            LineEnding::FF => "\u{000C}",
+            LineEnding::Nel => "\u{0085}",
+            LineEnding::LS => "\u{2028}",
+            LineEnding::PS => "\u{2029}",
+        }
+    }
+}
+
 /// Configuration for the Loggers
 ///
 /// All loggers print the message in the following form:
@@ -90,26 +638,74 @@ pub enum LineEnding {
 /// Construct using [`Default`](Config::default) or using [`ConfigBuilder`]
 #[derive(Debug, Clone)]
 pub struct Config {
+    pub(crate) banner: Option<String>,
+    pub(crate) prefix: Option<String>,
+    pub(crate) sequence: LevelFilter,
+    pub(crate) sequence_mode: SequenceMode,
+    pub(crate) sequence_counter: SequenceCounter,
+    pub(crate) record_counter: RecordCounter,
     pub(crate) time: LevelFilter,
+    pub(crate) hostname: LevelFilter,
+    pub(crate) hostname_override: Option<String>,
+    pub(crate) pid: LevelFilter,
     pub(crate) level: LevelFilter,
     pub(crate) level_padding: LevelPadding,
+    pub(crate) level_labels: [&'static str; 6],
+    pub(crate) level_icons: [Option<&'static str>; 6],
+    pub(crate) level_icon_only: bool,
     pub(crate) thread: LevelFilter,
     pub(crate) thread_log_mode: ThreadLogMode,
     pub(crate) thread_padding: ThreadPadding,
     pub(crate) target: LevelFilter,
     pub(crate) target_padding: TargetPadding,
+    pub(crate) target_abbreviation: TargetAbbreviation,
+    pub(crate) target_truncation: TargetTruncation,
+    pub(crate) target_aliases: Cow<'static, [(Cow<'static, str>, Cow<'static, str>)]>,
     pub(crate) location: LevelFilter,
+    pub(crate) location_basename: bool,
+    pub(crate) location_separator: &'static str,
+    pub(crate) location_line: bool,
     pub(crate) module: LevelFilter,
+    pub(crate) module_only_on_target_override: bool,
     pub(crate) time_format: TimeFormat,
     pub(crate) time_offset: UtcOffset,
+    pub(crate) time_cache: Option<TimeCache>,
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) flush_level: LevelFilter,
+    #[cfg(feature = "local-offset")]
+    pub(crate) local_offset: Option<LocalOffset>,
     pub(crate) filter_allow: Cow<'static, [Cow<'static, str>]>,
     pub(crate) filter_ignore: Cow<'static, [Cow<'static, str>]>,
+    pub(crate) filter_level: Cow<'static, [(Cow<'static, str>, LevelFilter)]>,
+    pub(crate) filter: Option<RecordFilter>,
+    #[cfg(feature = "termcolor")]
+    pub(crate) level_style: [Style; 6],
+    #[cfg(feature = "termcolor")]
+    pub(crate) colorize_line: LevelFilter,
+    #[cfg(feature = "termcolor")]
+    pub(crate) time_style: Style,
+    #[cfg(feature = "termcolor")]
+    pub(crate) thread_style: Style,
+    #[cfg(feature = "termcolor")]
+    pub(crate) target_style: Style,
+    #[cfg(feature = "termcolor")]
+    pub(crate) location_hyperlink: Option<String>,
+    #[cfg(feature = "termcolor")]
+    pub(crate) highlights: Vec<(Highlight, Style)>,
+    #[cfg(feature = "console-attention")]
+    pub(crate) error_attention: ErrorAttention,
+    #[cfg(feature = "console-attention")]
+    pub(crate) error_counter: RecordCounter,
     #[cfg(feature = "termcolor")]
-    pub(crate) level_color: [Option<Color>; 6],
     pub(crate) write_log_enable_colors: bool,
     #[cfg(feature = "paris")]
     pub(crate) enable_paris_formatting: bool,
     pub(crate) line_ending: String,
+    pub(crate) strip_ansi: bool,
+    pub(crate) multi_line_mode: MultiLineMode,
+    pub(crate) header_alignment: HeaderAlignment,
+    pub(crate) header_width: HeaderWidth,
+    pub(crate) escape_control_chars: bool,
 }
 
 impl Config {
@@ -117,6 +713,120 @@ impl Config {
     pub fn builder() -> ConfigBuilder {
         ConfigBuilder::new()
     }
+
+    /// Config logging just the message, e.g. `your_message`
+    pub fn minimal() -> Config {
+        ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_max_level(LevelFilter::Off)
+            .set_thread_level(LevelFilter::Off)
+            .set_target_level(LevelFilter::Off)
+            .set_location_level(LevelFilter::Off)
+            .set_module_level(LevelFilter::Off)
+            .build()
+    }
+
+    /// Config for command line tools logging the level and the message, e.g. `[WARN] your_message`
+    ///
+    /// The level is colored, when used with a `TermLogger`.
+    pub fn cli() -> Config {
+        ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_max_level(LevelFilter::Error)
+            .set_thread_level(LevelFilter::Off)
+            .set_target_level(LevelFilter::Off)
+            .set_location_level(LevelFilter::Off)
+            .set_module_level(LevelFilter::Off)
+            .build()
+    }
+
+    /// Config for services logging the rfc3339 UTC time, level, thread, target and message,
+    /// e.g. `2024-01-01T12:00:00.123456Z [WARN ] (1) my_crate::db: your_message`
+    pub fn server() -> Config {
+        ConfigBuilder::new()
+            .set_time_level(LevelFilter::Error)
+            .set_time_format_rfc3339()
+            .set_time_offset(UtcOffset::UTC)
+            .set_max_level(LevelFilter::Error)
+            .set_level_padding(LevelPadding::Right)
+            .set_thread_level(LevelFilter::Error)
+            .set_target_level(LevelFilter::Error)
+            .set_location_level(LevelFilter::Off)
+            .set_module_level(LevelFilter::Off)
+            .build()
+    }
+
+    /// Returns the number of spaces to pad a header of the given width with
+    pub(crate) fn header_padding(&self, width: usize) -> usize {
+        match self.header_alignment {
+            HeaderAlignment::Off => 0,
+            HeaderAlignment::Fixed(min_width) => min_width.saturating_sub(width),
+            HeaderAlignment::Auto => {
+                let widest = self.header_width.0.fetch_max(width, Ordering::Relaxed);
+                widest.saturating_sub(width)
+            }
+        }
+    }
+
+    /// Returns the next sequence number for a record
+    pub(crate) fn next_sequence(&self) -> u64 {
+        let counter = match self.sequence_mode {
+            SequenceMode::PerLogger => &self.sequence_counter.0,
+            SequenceMode::Global => &GLOBAL_SEQUENCE,
+        };
+        counter.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Counts a record written by the logger owning this config
+    pub(crate) fn count_record(&self) {
+        self.record_counter.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of records written by the logger owning this config
+    pub(crate) fn records_written(&self) -> u64 {
+        self.record_counter.0.load(Ordering::Relaxed)
+    }
+
+    /// Counts an error signaled by the logger owning this config, returning the number of errors so far
+    #[cfg(feature = "console-attention")]
+    pub(crate) fn count_error(&self) -> u64 {
+        self.error_counter.0.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Offset of the logged time, which might change over time for the local offset
+    pub(crate) fn time_offset(&self) -> UtcOffset {
+        #[cfg(feature = "local-offset")]
+        if let Some(local_offset) = &self.local_offset {
+            return local_offset.get();
+        }
+        self.time_offset
+    }
+
+    /// The current time according to the configured clock
+    /// (or the time of the `LogRecordOwned` currently rendered)
+    pub(crate) fn now(&self) -> SystemTime {
+        crate::loggers::logging::snapshot_time().unwrap_or_else(|| self.clock.now())
+    }
+
+    /// Renders a snapshot of a record into `write`, exactly like a `WriteLogger` with this config
+    ///
+    /// The time and thread of the snapshot are printed instead of the current ones,
+    /// except for the uptime and delta time formats, which measure the time of rendering.
+    /// Nothing is written, if the record is rejected by the filters of this config.
+    ///
+    /// See [`LogRecordOwned`] for an example.
+    pub fn render<W: Write>(&self, record: &LogRecordOwned, write: &mut W) -> Result<(), Error> {
+        record.with_record(|record| crate::loggers::logging::try_log(self, record, write))
+    }
+
+    /// Width of the longest level label, used for padding
+    pub(crate) fn level_label_width(&self) -> usize {
+        self.level_labels[1..]
+            .iter()
+            .map(|label| label.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 /// Builder for the Logger Configurations (`Config`)
@@ -142,18 +852,90 @@ impl ConfigBuilder {
         ConfigBuilder(Config::default())
     }
 
-    /// Set a custom line ending
+    /// Set a custom line ending (default is `LineEnding::LF`)
+    ///
+    /// Use `LineEnding::platform()` for the native line ending of the platform.
     pub fn set_line_ending(&mut self, line_ending: LineEnding) -> &mut ConfigBuilder {
-        match line_ending {
-            LineEnding::LF => self.0.line_ending = String::from("\u{000A}"),
-            LineEnding::CR => self.0.line_ending = String::from("\u{000D}"),
-            LineEnding::Crlf => self.0.line_ending = String::from("\u{000D}\u{000A}"),
-            LineEnding::VT => self.0.line_ending = String::from("\u{000B}"),
-            LineEnding::FF => self.0.line_ending = String::from("\u{000C}"),
-            LineEnding::Nel => self.0.line_ending = String::from("\u{0085}"),
-            LineEnding::LS => self.0.line_ending = String::from("\u{2028}"),
-            LineEnding::PS => self.0.line_ending = String::from("\u{2029}"),
-        }
+        self.0.line_ending = String::from(line_ending.as_str());
+        self
+    }
+
+    /// Set at which level and above (more verbose) the hostname shall be logged (default is Off)
+    ///
+    /// The hostname is determined once using the `hostname` feature, unless set explicitly
+    /// via [`ConfigBuilder::set_hostname`]. Without either, `<unknown>` is logged.
+    pub fn set_hostname_level(&mut self, hostname: LevelFilter) -> &mut ConfigBuilder {
+        self.0.hostname = hostname;
+        self
+    }
+
+    /// Set the hostname to be logged instead of the one of the machine, or None to use the latter (default is None)
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, LevelFilter};
+    /// let config = ConfigBuilder::new()
+    ///     .set_hostname_level(LevelFilter::Error)
+    ///     .set_hostname(Some("web-01"))
+    ///     .build();
+    /// ```
+    pub fn set_hostname(&mut self, hostname: Option<&str>) -> &mut ConfigBuilder {
+        self.0.hostname_override = hostname.map(String::from);
+        self
+    }
+
+    /// Log a banner with the name and version of the application right after the logger is initialized
+    /// (default is none)
+    ///
+    /// The banner additionally contains the version of simplelog, the level and the active loggers
+    /// with their levels and sinks (see `SharedLogger::sink_description`),
+    /// which makes restarts of the application easy to find in a shared log file.
+    /// It is logged at `Info` level, or the level of the logger, if that is less verbose.
+    /// For a [`CombinedLogger`](crate::CombinedLogger) the banner and level of the first logger setting one is used.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::*;
+    /// let config = ConfigBuilder::new()
+    ///     .set_banner(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+    ///     .build();
+    /// // logs e.g. `[INFO] my_app 1.0.0 started (simplelog 0.12.0, level INFO), loggers: SimpleLogger (INFO) to stdout, errors to stderr`
+    /// let _ = SimpleLogger::init(LevelFilter::Info, config);
+    /// ```
+    pub fn set_banner(&mut self, app_name: &str, app_version: &str) -> &mut ConfigBuilder {
+        self.0.banner = Some(format!("{} {}", app_name, app_version));
+        self
+    }
+
+    /// Set a tag printed in front of every record, or an empty string to disable it (default is none)
+    ///
+    /// This marks which logger emitted a line, when several loggers of a
+    /// [`CombinedLogger`](crate::CombinedLogger) write into the same file.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::ConfigBuilder;
+    /// let config = ConfigBuilder::new()
+    ///     .set_prefix("auth")
+    ///     .build();
+    /// ```
+    pub fn set_prefix(&mut self, prefix: &str) -> &mut ConfigBuilder {
+        self.0.prefix = if prefix.is_empty() {
+            None
+        } else {
+            Some(prefix.to_owned())
+        };
+        self
+    }
+
+    /// Set at which level and above (more verbose) the process id shall be logged (default is Off)
+    ///
+    /// This makes it possible to tell apart multiple instances of a program logging into the same file.
+    pub fn set_pid_level(&mut self, pid: LevelFilter) -> &mut ConfigBuilder {
+        self.0.pid = pid;
         self
     }
 
@@ -163,45 +945,337 @@ impl ConfigBuilder {
         self
     }
 
-    /// Set at which level and  above (more verbose) the current time shall be logged (default is Error)
-    pub fn set_time_level(&mut self, time: LevelFilter) -> &mut ConfigBuilder {
-        self.0.time = time;
+    /// Set at which level and above (more verbose) a sequence number shall be logged (default is Off)
+    pub fn set_sequence_level(&mut self, sequence: LevelFilter) -> &mut ConfigBuilder {
+        self.0.sequence = sequence;
+        self
+    }
+
+    /// Set if the sequence numbers are counted per logger or for the whole process (default is PerLogger)
+    ///
+    /// Sequence numbers allow to detect missing or reordered lines, when logs are merged.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, LevelFilter, SequenceMode};
+    /// let config = ConfigBuilder::new()
+    ///     .set_sequence_level(LevelFilter::Error)
+    ///     .set_sequence_mode(SequenceMode::Global)
+    ///     .build();
+    /// ```
+    pub fn set_sequence_mode(&mut self, mode: SequenceMode) -> &mut ConfigBuilder {
+        self.0.sequence_mode = mode;
+        self
+    }
+
+    /// Set at which level and  above (more verbose) the current time shall be logged (default is Error)
+    pub fn set_time_level(&mut self, time: LevelFilter) -> &mut ConfigBuilder {
+        self.0.time = time;
+        self
+    }
+
+    /// Set at which level and above (more verbose) the thread id shall be logged. (default is Debug)
+    pub fn set_thread_level(&mut self, thread: LevelFilter) -> &mut ConfigBuilder {
+        self.0.thread = thread;
+        self
+    }
+
+    /// Set at which level and above (more verbose) the target shall be logged. (default is Debug)
+    pub fn set_target_level(&mut self, target: LevelFilter) -> &mut ConfigBuilder {
+        self.0.target = target;
+        self
+    }
+
+    /// Set how the thread should be padded
+    pub fn set_target_padding(&mut self, padding: TargetPadding) -> &mut ConfigBuilder {
+        self.0.target_padding = padding;
+        self
+    }
+
+    /// Set how targets longer than the width of the target padding should be truncated (default is Off)
+    ///
+    /// Together with [`TargetPadding::Left`] or [`TargetPadding::Right`] this guarantees a fixed width target column.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, TargetPadding, TargetTruncation};
+    /// let config = ConfigBuilder::new()
+    ///     .set_target_padding(TargetPadding::Right(15))
+    ///     .set_target_truncation(TargetTruncation::Start("…"))
+    ///     .build();
+    /// ```
+    pub fn set_target_truncation(&mut self, truncation: TargetTruncation) -> &mut ConfigBuilder {
+        self.0.target_truncation = truncation;
+        self
+    }
+
+    /// Set how long targets should be abbreviated (default is Off)
+    pub fn set_target_abbreviation(
+        &mut self,
+        abbreviation: TargetAbbreviation,
+    ) -> &mut ConfigBuilder {
+        self.0.target_abbreviation = abbreviation;
+        self
+    }
+
+    /// Add an alias printed instead of the targets matching the given entry
+    ///
+    /// Entries are matched like allow and ignore filters, if multiple match, the longest one is used.
+    /// Filters still apply to the original target. This keeps the printed channel names stable,
+    /// while the module tree of the application changes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use simplelog::ConfigBuilder;
+    /// // prints `core` for `my_app::internal` and all its submodules
+    /// let config = ConfigBuilder::new()
+    ///     .add_target_alias_str("my_app::internal", "core")
+    ///     .build();
+    /// ```
+    pub fn add_target_alias_str(
+        &mut self,
+        target: &'static str,
+        alias: &'static str,
+    ) -> &mut ConfigBuilder {
+        let mut list = Vec::from(&*self.0.target_aliases);
+        list.push((Cow::Borrowed(target), Cow::Borrowed(alias)));
+        self.0.target_aliases = Cow::Owned(list);
+        self
+    }
+
+    /// Add an alias printed instead of the targets matching the given entry
+    ///
+    /// Like `add_target_alias_str`, but takes owned strings, e.g. read from a configuration file.
+    pub fn add_target_alias(&mut self, target: String, alias: String) -> &mut ConfigBuilder {
+        let mut list = Vec::from(&*self.0.target_aliases);
+        list.push((Cow::Owned(target), Cow::Owned(alias)));
+        self.0.target_aliases = Cow::Owned(list);
+        self
+    }
+
+    /// Clear target aliases.
+    /// If none are specified, targets are printed as they are
+    pub fn clear_target_aliases(&mut self) -> &mut ConfigBuilder {
+        self.0.target_aliases = Cow::Borrowed(&[]);
+        self
+    }
+
+    /// Show more details for the verbosity given by the number of `-v` and `-q` flags
+    ///
+    /// Starting at `-vv` (see [`level_from_verbosity`](crate::level_from_verbosity)) the thread and target
+    /// are logged for all records, at `-vvv` the source code reference as well. Otherwise nothing is changed.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::*;
+    /// # let (verbose, quiet) = (2, 0);
+    /// let config = ConfigBuilder::new().apply_verbosity(verbose, quiet).build();
+    /// let _ = SimpleLogger::init(level_from_verbosity(verbose, quiet), config);
+    /// ```
+    pub fn apply_verbosity(&mut self, verbose: u8, quiet: u8) -> &mut ConfigBuilder {
+        let level = crate::level_from_verbosity(verbose, quiet);
+        if level >= LevelFilter::Debug {
+            self.set_thread_level(LevelFilter::Error);
+            self.set_target_level(LevelFilter::Error);
+        }
+        if level >= LevelFilter::Trace {
+            self.set_location_level(LevelFilter::Error);
+        }
+        self
+    }
+
+    /// Set at which level and above (more verbose) a source code reference shall be logged (default is Trace)
+    pub fn set_location_level(&mut self, location: LevelFilter) -> &mut ConfigBuilder {
+        self.0.location = location;
+        self
+    }
+
+    /// Set if only the file name should be logged as part of the source code reference
+    /// instead of its full path (default is false)
+    pub fn set_location_basename(&mut self, basename: bool) -> &mut ConfigBuilder {
+        self.0.location_basename = basename;
+        self
+    }
+
+    /// Set the separator between file and line of the source code reference (default is ":")
+    pub fn set_location_separator(&mut self, separator: &'static str) -> &mut ConfigBuilder {
+        self.0.location_separator = separator;
+        self
+    }
+
+    /// Set if the line number should be logged as part of the source code reference (default is true)
+    pub fn set_location_line(&mut self, line: bool) -> &mut ConfigBuilder {
+        self.0.location_line = line;
+        self
+    }
+
+    /// Render the source code reference as a clickable hyperlink (if the logger and terminal support it),
+    /// or None to disable hyperlinks (default is None)
+    ///
+    /// The url is created from the given template by replacing `{file}` with the percent-encoded absolute
+    /// path of the source file and `{line}` with the line number. Relative source paths are resolved
+    /// against the working directory at the time the logger is created.
+    /// Hyperlinks are only rendered by a `TermLogger` writing to a terminal, not if the output is redirected.
+    /// Terminals not supporting OSC 8 hyperlinks will just print the source code reference.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::ConfigBuilder;
+    /// let config = ConfigBuilder::new()
+    ///     .set_location_hyperlink(Some("vscode://file/{file}:{line}"))
+    ///     .build();
+    /// ```
+    #[cfg(feature = "termcolor")]
+    pub fn set_location_hyperlink(&mut self, url_template: Option<&str>) -> &mut ConfigBuilder {
+        self.0.location_hyperlink = url_template.map(String::from);
+        self
+    }
+
+    /// Add a text to be highlighted in the messages printed by the `TermLogger`
+    ///
+    /// Every occurrence of `text` is printed in the given style (or color). If highlights overlap,
+    /// the one starting first wins, and the one added first for highlights starting at the same position.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{Color, ConfigBuilder, Style};
+    /// let config = ConfigBuilder::new()
+    ///     .add_highlight("timeout", Color::Red)
+    ///     .add_highlight("retrying", Style::new().bold())
+    ///     .build();
+    /// ```
+    #[cfg(feature = "termcolor")]
+    pub fn add_highlight<S: Into<Style>>(&mut self, text: &str, style: S) -> &mut ConfigBuilder {
+        self.0
+            .highlights
+            .push((Highlight::Text(text.to_owned()), style.into()));
+        self
+    }
+
+    /// Add a regular expression, whose matches are highlighted in the messages printed by the `TermLogger`
+    ///
+    /// Behaves like [`add_highlight`](ConfigBuilder::add_highlight) otherwise.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, Regex, Style};
+    /// let config = ConfigBuilder::new()
+    ///     .add_highlight_regex(Regex::new(r"\b\d{3,}ms\b").unwrap(), Style::new().bold())
+    ///     .build();
+    /// ```
+    #[cfg(all(feature = "termcolor", feature = "regex"))]
+    pub fn add_highlight_regex<S: Into<Style>>(
+        &mut self,
+        regex: regex::Regex,
+        style: S,
+    ) -> &mut ConfigBuilder {
+        self.0
+            .highlights
+            .push((Highlight::Regex(regex), style.into()));
+        self
+    }
+
+    /// Remove all highlights
+    #[cfg(feature = "termcolor")]
+    pub fn clear_highlights(&mut self) -> &mut ConfigBuilder {
+        self.0.highlights.clear();
+        self
+    }
+
+    /// Set a signal drawing attention to error records in an interactive console (default is none)
+    ///
+    /// Only the `TermLogger` writing to stdout/stderr signals errors, and only if its
+    /// error stream is a terminal. This gives long-running interactive tools a non-intrusive hint,
+    /// that something went wrong, while their window is in the background.
+    #[cfg(feature = "console-attention")]
+    pub fn set_error_attention(&mut self, attention: ErrorAttention) -> &mut ConfigBuilder {
+        self.0.error_attention = attention;
         self
     }
 
-    /// Set at which level and above (more verbose) the thread id shall be logged. (default is Debug)
-    pub fn set_thread_level(&mut self, thread: LevelFilter) -> &mut ConfigBuilder {
-        self.0.thread = thread;
+    /// Set at which level and above (more verbose) a module shall be logged (default is Off)
+    pub fn set_module_level(&mut self, module: LevelFilter) -> &mut ConfigBuilder {
+        self.0.module = module;
         self
     }
 
-    /// Set at which level and above (more verbose) the target shall be logged. (default is Debug)
-    pub fn set_target_level(&mut self, target: LevelFilter) -> &mut ConfigBuilder {
-        self.0.target = target;
+    /// Set if the module should only be logged, when the target of a record differs from its module path,
+    /// e.g. because it was set explicitly via `info!(target: "audit", ...)` (default is false)
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, LevelFilter};
+    /// let config = ConfigBuilder::new()
+    ///     .set_module_level(LevelFilter::Error)
+    ///     .set_module_only_on_target_override(true)
+    ///     .build();
+    /// ```
+    pub fn set_module_only_on_target_override(
+        &mut self,
+        only_on_override: bool,
+    ) -> &mut ConfigBuilder {
+        self.0.module_only_on_target_override = only_on_override;
         self
     }
 
-    /// Set how the thread should be padded
-    pub fn set_target_padding(&mut self, padding: TargetPadding) -> &mut ConfigBuilder {
-        self.0.target_padding = padding;
+    /// Set how the levels should be padded, when logging (default is Off)
+    pub fn set_level_padding(&mut self, padding: LevelPadding) -> &mut ConfigBuilder {
+        self.0.level_padding = padding;
         self
     }
 
-    /// Set at which level and above (more verbose) a source code reference shall be logged (default is Trace)
-    pub fn set_location_level(&mut self, location: LevelFilter) -> &mut ConfigBuilder {
-        self.0.location = location;
+    /// Set the label printed for the given level (default is the uppercase name of the level, e.g. `ERROR`)
+    ///
+    /// Padded levels are padded to the width of the longest label.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, Level};
+    /// let config = ConfigBuilder::new()
+    ///     .set_level_label(Level::Error, "E")
+    ///     .set_level_label(Level::Warn, "W")
+    ///     .set_level_label(Level::Info, "I")
+    ///     .set_level_label(Level::Debug, "D")
+    ///     .set_level_label(Level::Trace, "T")
+    ///     .build();
+    /// ```
+    pub fn set_level_label(&mut self, level: Level, label: &'static str) -> &mut ConfigBuilder {
+        self.0.level_labels[level as usize] = label;
         self
     }
 
-    /// Set at which level and above (more verbose) a module shall be logged (default is Off)
-    pub fn set_module_level(&mut self, module: LevelFilter) -> &mut ConfigBuilder {
-        self.0.module = module;
+    /// Set an icon (e.g. an emoji) printed in front of the given level, or None to print no icon (default is None)
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, Level};
+    /// let config = ConfigBuilder::new()
+    ///     .set_level_icon(Level::Error, Some("✗"))
+    ///     .set_level_icon(Level::Warn, Some("⚠"))
+    ///     .set_level_icon(Level::Info, Some("ℹ"))
+    ///     .build();
+    /// ```
+    pub fn set_level_icon(
+        &mut self,
+        level: Level,
+        icon: Option<&'static str>,
+    ) -> &mut ConfigBuilder {
+        self.0.level_icons[level as usize] = icon;
         self
     }
 
-    /// Set how the levels should be padded, when logging (default is Off)
-    pub fn set_level_padding(&mut self, padding: LevelPadding) -> &mut ConfigBuilder {
-        self.0.level_padding = padding;
+    /// set if you want to print just the icon instead of the level for levels with an icon (default is Off)
+    pub fn set_level_icon_only(&mut self, icon_only: bool) -> &mut ConfigBuilder {
+        self.0.level_icon_only = icon_only;
         self
     }
 
@@ -221,7 +1295,67 @@ impl ConfigBuilder {
     /// or None to use the default foreground color
     #[cfg(feature = "termcolor")]
     pub fn set_level_color(&mut self, level: Level, color: Option<Color>) -> &mut ConfigBuilder {
-        self.0.level_color[level as usize] = color;
+        self.0.level_style[level as usize].fg = color;
+        self
+    }
+
+    /// Set the colors used for printing all levels at once (if the logger supports it).
+    ///
+    /// The array is indexed by `Level as usize`, so the first entry is unused.
+    /// `None` uses the default foreground color.
+    #[cfg(feature = "termcolor")]
+    pub fn set_level_colors(&mut self, colors: [Option<Color>; 6]) -> &mut ConfigBuilder {
+        for (style, color) in self.0.level_style.iter_mut().zip(colors) {
+            style.fg = color;
+        }
+        self
+    }
+
+    /// Replace the styles of all levels with the ones of a built-in [`Theme`]
+    /// (default is [`Theme::Default`])
+    #[cfg(feature = "termcolor")]
+    pub fn set_theme(&mut self, theme: Theme) -> &mut ConfigBuilder {
+        self.0.level_style = theme.level_styles();
+        self
+    }
+
+    /// Set the style used for printing the level (if the logger supports it).
+    ///
+    /// This replaces the color set by [`ConfigBuilder::set_level_color`].
+    #[cfg(feature = "termcolor")]
+    pub fn set_level_style(&mut self, level: Level, style: Style) -> &mut ConfigBuilder {
+        self.0.level_style[level as usize] = style;
+        self
+    }
+
+    /// Set the style used for printing the time (if the logger supports it)
+    #[cfg(feature = "termcolor")]
+    pub fn set_time_style(&mut self, style: Style) -> &mut ConfigBuilder {
+        self.0.time_style = style;
+        self
+    }
+
+    /// Set the style used for printing the thread id/name (if the logger supports it)
+    #[cfg(feature = "termcolor")]
+    pub fn set_thread_style(&mut self, style: Style) -> &mut ConfigBuilder {
+        self.0.thread_style = style;
+        self
+    }
+
+    /// Set the style used for printing the target (if the logger supports it)
+    #[cfg(feature = "termcolor")]
+    pub fn set_target_style(&mut self, style: Style) -> &mut ConfigBuilder {
+        self.0.target_style = style;
+        self
+    }
+
+    /// Set up to which level the whole line, not just the level, is printed
+    /// in the style of the level (if the logger supports it) (default is Off)
+    ///
+    /// E.g. `LevelFilter::Warn` colors every line of `Error` and `Warn` records.
+    #[cfg(feature = "termcolor")]
+    pub fn set_colorize_line(&mut self, level: LevelFilter) -> &mut ConfigBuilder {
+        self.0.colorize_line = level;
         self
     }
 
@@ -251,6 +1385,70 @@ impl ConfigBuilder {
         self
     }
 
+    /// Set time format from a format description parsed at runtime, e.g. taken from a config file
+    /// or a command line flag.
+    ///
+    /// The syntax is the same as for [`set_time_format_custom`](ConfigBuilder::set_time_format_custom),
+    /// an error is returned if the format description is invalid.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::ConfigBuilder;
+    /// let format = "[year]-[month]-[day] [hour]:[minute]:[second]";
+    /// let config = ConfigBuilder::new()
+    ///     .set_time_format_str(format)
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// assert!(ConfigBuilder::new().set_time_format_str("[hour").is_err());
+    /// ```
+    pub fn set_time_format_str(
+        &mut self,
+        time_format: &str,
+    ) -> Result<&mut ConfigBuilder, InvalidFormatDescription> {
+        let format = time::format_description::parse_owned::<1>(time_format)?;
+        self.0.time_format = TimeFormat::Owned(format);
+        Ok(self)
+    }
+
+    /// Set time format to a chrono format string, e.g. `%Y-%m-%d %H:%M:%S%.3f`,
+    /// applied to the time in the configured time offset.
+    ///
+    /// The syntax can be found in the [`chrono` documentation](https://docs.rs/chrono/latest/chrono/format/strftime/index.html).
+    /// An invalid format string is reported by `try_build`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, ConfigError};
+    /// let result = ConfigBuilder::new().set_time_format_chrono("%Y-%m-%d %Q").try_build();
+    /// assert!(matches!(result, Err(ConfigError::InvalidChronoFormat(_))));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn set_time_format_chrono(&mut self, time_format: &str) -> &mut ConfigBuilder {
+        self.0.time_format = chrono_time_format(time_format, false);
+        self
+    }
+
+    /// Set time format to a chrono format string, applied to the local time as determined by `chrono::Local`
+    /// when the record is logged. The time offset has no effect on this format.
+    /// An invalid format string is reported by `try_build`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::ConfigBuilder;
+    /// let config = ConfigBuilder::new()
+    ///     .set_time_format_chrono_local("%Y-%m-%d %H:%M:%S%.3f")
+    ///     .build();
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn set_time_format_chrono_local(&mut self, time_format: &str) -> &mut ConfigBuilder {
+        self.0.time_format = chrono_time_format(time_format, true);
+        self
+    }
+
     /// Set time format string to use rfc2822.
     pub fn set_time_format_rfc2822(&mut self) -> &mut ConfigBuilder {
         self.0.time_format = TimeFormat::Rfc2822;
@@ -263,9 +1461,110 @@ impl ConfigBuilder {
         self
     }
 
+    /// Set time format to a numeric unix timestamp of the given precision, e.g. `1700000000123`
+    /// for milliseconds. The time offset has no effect on this format.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, UnixPrecision};
+    /// let config = ConfigBuilder::new()
+    ///     .set_time_format_unix(UnixPrecision::Millis)
+    ///     .build();
+    /// ```
+    pub fn set_time_format_unix(&mut self, precision: UnixPrecision) -> &mut ConfigBuilder {
+        self.0.time_format = TimeFormat::Unix(precision);
+        self
+    }
+
+    /// Set time format to the seconds elapsed since the logger was initialized,
+    /// printed with the given amount of decimal places, e.g. `[   3.042]`.
+    ///
+    /// Like the time format used by `dmesg` this has a fixed width (for uptimes below one day)
+    /// and is handy to see how long things took, e.g. when benchmarking cli tools.
+    /// The time offset has no effect on this format.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::ConfigBuilder;
+    /// let config = ConfigBuilder::new()
+    ///     .set_time_format_uptime(3)
+    ///     .build();
+    /// ```
+    pub fn set_time_format_uptime(&mut self, precision: usize) -> &mut ConfigBuilder {
+        self.0.time_format = TimeFormat::Uptime(precision);
+        self
+    }
+
+    /// Set time format to the time elapsed since the previous record of the same logger, e.g. `+12.3ms`.
+    ///
+    /// This makes slow parts stand out when reading through verbose logs.
+    /// The time offset has no effect on this format.
+    pub fn set_time_format_delta(&mut self) -> &mut ConfigBuilder {
+        self.0.time_format = TimeFormat::Delta(DeltaClock::default());
+        self
+    }
+
+    /// Cache the formatted time and only format it again, once the time moved on to the next
+    /// interval of the given granularity, or None to format the time for every record (default is None)
+    ///
+    /// Formatting the time can dominate the cost of logging at very high throughput.
+    /// With a granularity of one second, a format showing subseconds logs the time of the first
+    /// record of each second. Uptime, delta and unix time formats are not cached.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::ConfigBuilder;
+    /// # use std::time::Duration;
+    /// let config = ConfigBuilder::new()
+    ///     .set_time_cache(Some(Duration::from_secs(1)))
+    ///     .build();
+    /// ```
+    pub fn set_time_cache(&mut self, granularity: Option<Duration>) -> &mut ConfigBuilder {
+        self.0.time_cache = granularity
+            .filter(|granularity| !granularity.is_zero())
+            .map(TimeCache::new);
+        self
+    }
+
+    /// Set the clock the logged time is taken from (default is [`SystemClock`])
+    ///
+    /// Use a [`FixedClock`](crate::FixedClock) or [`SteppingClock`](crate::SteppingClock)
+    /// to get deterministic output, e.g. for snapshot tests.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) -> &mut ConfigBuilder {
+        self.0.clock = Arc::new(clock);
+        self
+    }
+
+    /// Set up to which level records are flushed to the terminal immediately (default is Trace)
+    ///
+    /// Flushing every record is slow, e.g. when trace logging to a terminal over ssh.
+    /// Less severe records are buffered until a more severe one is logged or the logger is flushed,
+    /// so make sure to call [`shutdown`](crate::shutdown) before exiting to not lose the last records.
+    /// Only affects the `TermLogger`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, LevelFilter};
+    /// let config = ConfigBuilder::new()
+    ///     .set_flush_level(LevelFilter::Warn)
+    ///     .build();
+    /// ```
+    pub fn set_flush_level(&mut self, level: LevelFilter) -> &mut ConfigBuilder {
+        self.0.flush_level = level;
+        self
+    }
+
     /// Set offset used for logging time (default is UTC)
     pub fn set_time_offset(&mut self, offset: UtcOffset) -> &mut ConfigBuilder {
         self.0.time_offset = offset;
+        #[cfg(feature = "local-offset")]
+        {
+            self.0.local_offset = None;
+        }
         self
     }
 
@@ -281,14 +1580,30 @@ impl ConfigBuilder {
         match UtcOffset::current_local_offset() {
             Ok(offset) => {
                 self.0.time_offset = offset;
+                self.0.local_offset = None;
                 Ok(self)
             }
             Err(_) => Err(self),
         }
     }
 
+    /// Sets the offset used to the local time offset, determined again periodically while logging,
+    /// so that long running programs pick up daylight saving time changes.
+    /// (overriding values previously set by [`ConfigBuilder::set_time_offset`]).
+    ///
+    /// Just like [`ConfigBuilder::set_time_offset_to_local`], determining the offset may fail,
+    /// e.g. once the program is multi-threaded (unless using `RUSTFLAGS="--cfg unsound_local_offset"`).
+    /// In that case the last known offset keeps being used.
+    #[cfg(feature = "local-offset")]
+    pub fn set_time_offset_to_local_dynamic(&mut self) -> &mut ConfigBuilder {
+        self.0.local_offset = Some(LocalOffset::new(self.0.time_offset));
+        self
+    }
+
     /// set if you want to write colors in the logfile (default is Off)
-    #[cfg(feature = "ansi_term")]
+    ///
+    /// The level is written using ANSI escape codes in the style set by [`ConfigBuilder::set_level_style`].
+    #[cfg(feature = "termcolor")]
     pub fn set_write_log_enable_colors(&mut self, local: bool) -> &mut ConfigBuilder {
         self.0.write_log_enable_colors = local;
         self
@@ -303,10 +1618,55 @@ impl ConfigBuilder {
         self
     }
 
+    /// set if you want ANSI escape sequences (e.g. colors) to be removed from the logged messages (default is Off)
+    ///
+    /// Useful for loggers writing to files, if some crates emit already colored messages.
+    pub fn set_strip_ansi(&mut self, strip_ansi: bool) -> &mut ConfigBuilder {
+        self.0.strip_ansi = strip_ansi;
+        self
+    }
+
+    /// set if you want control characters in the logged messages to be escaped (default is Off)
+    ///
+    /// Line breaks, carriage returns, escape characters, etc. are printed as `\n`, `\r`, `\x1b`, ...,
+    /// protecting e.g. audit logs containing user supplied strings against forged lines.
+    /// Tabs are kept as they are. Records still end with the configured line ending,
+    /// any occurrence of it within the message is escaped, so every record is exactly one line.
+    pub fn set_escape_control_chars(&mut self, escape: bool) -> &mut ConfigBuilder {
+        self.0.escape_control_chars = escape;
+        self
+    }
+
+    /// Set how messages spanning multiple lines should be printed (default is Off)
+    pub fn set_multi_line_mode(&mut self, mode: MultiLineMode) -> &mut ConfigBuilder {
+        self.0.multi_line_mode = mode;
+        self
+    }
+
+    /// Set how the messages should be aligned (default is Off)
+    ///
+    /// The header (time, level, thread, target, ...) is padded with spaces, so all messages start in the same column,
+    /// no matter how wide the level or target of a record is.
+    ///
+    /// # Examples
+    /// ```
+    /// # use simplelog::{ConfigBuilder, HeaderAlignment};
+    /// let config = ConfigBuilder::new()
+    ///     .set_header_alignment(HeaderAlignment::Auto)
+    ///     .build();
+    /// ```
+    pub fn set_header_alignment(&mut self, alignment: HeaderAlignment) -> &mut ConfigBuilder {
+        self.0.header_alignment = alignment;
+        self
+    }
+
     /// Add allowed target filters.
     /// If any are specified, only records from targets matching one of these entries will be printed
     ///
     /// For example, `add_filter_allow_str("tokio::uds")` would allow only logging from the `tokio` crates `uds` module.
+    ///
+    /// Entries match any target starting with them. A `*` matches any sequence of characters,
+    /// so `add_filter_allow_str("my_crate::*::db")` allows the `db` module at any depth of `my_crate`.
     pub fn add_filter_allow_str(&mut self, filter_allow: &'static str) -> &mut ConfigBuilder {
         let mut list = Vec::from(&*self.0.filter_allow);
         list.push(Cow::Borrowed(filter_allow));
@@ -336,6 +1696,9 @@ impl ConfigBuilder {
     /// If any are specified, records from targets matching one of these entries will be ignored
     ///
     /// For example, `add_filter_ignore_str("tokio::uds")` would deny logging from the `tokio` crates `uds` module.
+    ///
+    /// Entries match any target starting with them. A `*` matches any sequence of characters,
+    /// so `add_filter_ignore_str("*::tests")` denies logging from all `tests` modules.
     pub fn add_filter_ignore_str(&mut self, filter_ignore: &'static str) -> &mut ConfigBuilder {
         let mut list = Vec::from(&*self.0.filter_ignore);
         list.push(Cow::Borrowed(filter_ignore));
@@ -361,10 +1724,133 @@ impl ConfigBuilder {
         self
     }
 
+    /// Add a level filter for targets matching the given entry.
+    /// Records from these targets, that are more verbose than `level`, will be ignored
+    ///
+    /// For example, `add_filter_level_str("sqlx", LevelFilter::Warn)` would only log warnings and errors of `sqlx`.
+    /// Entries are matched like allow and ignore filters, if multiple match, the longest one is used.
+    pub fn add_filter_level_str(
+        &mut self,
+        filter: &'static str,
+        level: LevelFilter,
+    ) -> &mut ConfigBuilder {
+        let mut list = Vec::from(&*self.0.filter_level);
+        list.push((Cow::Borrowed(filter), level));
+        self.0.filter_level = Cow::Owned(list);
+        self
+    }
+
+    /// Add a level filter for targets matching the given entry.
+    /// Records from these targets, that are more verbose than `level`, will be ignored
+    ///
+    /// For example, `add_filter_level(format!("{}::{}", "hyper", "proto"), LevelFilter::Info)` would ignore
+    /// debug and trace messages of the `hyper` crates `proto` module.
+    pub fn add_filter_level(&mut self, filter: String, level: LevelFilter) -> &mut ConfigBuilder {
+        let mut list = Vec::from(&*self.0.filter_level);
+        list.push((Cow::Owned(filter), level));
+        self.0.filter_level = Cow::Owned(list);
+        self
+    }
+
+    /// Clear level filters.
+    /// If none are specified, only the level of the logger applies
+    pub fn clear_filter_level(&mut self) -> &mut ConfigBuilder {
+        self.0.filter_level = Cow::Borrowed(&[]);
+        self
+    }
+
+    /// Set a predicate deciding which records are logged, in addition to the other filters (default is none).
+    /// Records for which it returns false will be ignored
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::ConfigBuilder;
+    /// let config = ConfigBuilder::new()
+    ///     .set_filter(Box::new(|metadata| !metadata.target().ends_with("::noisy")))
+    ///     .build();
+    /// ```
+    pub fn set_filter(
+        &mut self,
+        filter: Box<dyn Fn(&Metadata<'_>) -> bool + Send + Sync>,
+    ) -> &mut ConfigBuilder {
+        self.0.filter = Some(RecordFilter(Arc::from(filter)));
+        self
+    }
+
+    /// Remove the predicate set by [`ConfigBuilder::set_filter`]
+    pub fn clear_filter(&mut self) -> &mut ConfigBuilder {
+        self.0.filter = None;
+        self
+    }
+
     /// Build new `Config`
     pub fn build(&mut self) -> Config {
         self.0.clone()
     }
+
+    /// Build new `Config`, validating it first.
+    ///
+    /// Catches mistakes like time formats, which cannot be rendered,
+    /// when building the `Config` instead of when logging the first record.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use simplelog::{ConfigBuilder, ConfigError, TargetTruncation};
+    /// let result = ConfigBuilder::new()
+    ///     .set_target_truncation(TargetTruncation::End("…"))
+    ///     .try_build();
+    /// assert!(matches!(result, Err(ConfigError::TruncationWithoutPadding)));
+    ///
+    /// let result = ConfigBuilder::new().set_time_format_str("").unwrap().try_build();
+    /// assert!(matches!(result, Err(ConfigError::EmptyTimeFormat)));
+    /// ```
+    pub fn try_build(&mut self) -> Result<Config, ConfigError> {
+        use time::format_description::well_known::*;
+
+        let config = &self.0;
+        let time = time::OffsetDateTime::UNIX_EPOCH.to_offset(config.time_offset);
+        let res = match &config.time_format {
+            TimeFormat::Custom([]) => return Err(ConfigError::EmptyTimeFormat),
+            TimeFormat::Owned(OwnedFormatItem::Compound(items)) if items.is_empty() => {
+                return Err(ConfigError::EmptyTimeFormat)
+            }
+            TimeFormat::Custom(format) => time.format(format),
+            TimeFormat::Owned(format) => time.format(format),
+            TimeFormat::Rfc2822 => time.format(&Rfc2822),
+            TimeFormat::Rfc3339 => time.format(&Rfc3339),
+            #[cfg(feature = "chrono")]
+            TimeFormat::Chrono {
+                format,
+                valid: false,
+                ..
+            } => return Err(ConfigError::InvalidChronoFormat(format.clone())),
+            _ => Ok(String::new()),
+        };
+        if let Err(err) = res {
+            return Err(ConfigError::InvalidTimeFormat(err));
+        }
+
+        if config.target_truncation != TargetTruncation::Off
+            && matches!(config.target_padding, TargetPadding::Off)
+        {
+            return Err(ConfigError::TruncationWithoutPadding);
+        }
+
+        Ok(self.0.clone())
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn chrono_time_format(format: &str, local: bool) -> TimeFormat {
+    use chrono::format::{Item, StrftimeItems};
+
+    TimeFormat::Chrono {
+        format: format.to_string(),
+        local,
+        valid: !StrftimeItems::new(format).any(|item| item == Item::Error),
+    }
 }
 
 impl Default for ConfigBuilder {
@@ -376,35 +1862,76 @@ impl Default for ConfigBuilder {
 impl Default for Config {
     fn default() -> Config {
         Config {
+            banner: None,
+            prefix: None,
+            sequence: LevelFilter::Off,
+            sequence_mode: SequenceMode::PerLogger,
+            sequence_counter: SequenceCounter::default(),
+            record_counter: RecordCounter::default(),
             time: LevelFilter::Error,
+            hostname: LevelFilter::Off,
+            hostname_override: None,
+            pid: LevelFilter::Off,
             level: LevelFilter::Error,
             level_padding: LevelPadding::Off,
+            level_labels: ["", "ERROR", "WARN", "INFO", "DEBUG", "TRACE"],
+            level_icons: [None; 6],
+            level_icon_only: false,
             thread: LevelFilter::Debug,
             thread_log_mode: ThreadLogMode::IDs,
             thread_padding: ThreadPadding::Off,
             target: LevelFilter::Debug,
             target_padding: TargetPadding::Off,
+            target_abbreviation: TargetAbbreviation::Off,
+            target_truncation: TargetTruncation::Off,
+            target_aliases: Cow::Borrowed(&[]),
             location: LevelFilter::Trace,
+            location_basename: false,
+            location_separator: ":",
+            location_line: true,
             module: LevelFilter::Off,
+            module_only_on_target_override: false,
             time_format: TimeFormat::Custom(format_description!("[hour]:[minute]:[second]")),
             time_offset: UtcOffset::UTC,
+            time_cache: None,
+            clock: Arc::new(SystemClock),
+            flush_level: LevelFilter::Trace,
+            #[cfg(feature = "local-offset")]
+            local_offset: None,
             filter_allow: Cow::Borrowed(&[]),
             filter_ignore: Cow::Borrowed(&[]),
+            filter_level: Cow::Borrowed(&[]),
+            filter: None,
+            #[cfg(feature = "termcolor")]
             write_log_enable_colors: false,
 
             #[cfg(feature = "termcolor")]
-            level_color: [
-                None,                // Default foreground
-                Some(Color::Red),    // Error
-                Some(Color::Yellow), // Warn
-                Some(Color::Blue),   // Info
-                Some(Color::Cyan),   // Debug
-                Some(Color::White),  // Trace
-            ],
+            level_style: Theme::Default.level_styles(),
+            #[cfg(feature = "termcolor")]
+            colorize_line: LevelFilter::Off,
+            #[cfg(feature = "termcolor")]
+            time_style: Style::new(),
+            #[cfg(feature = "termcolor")]
+            thread_style: Style::new(),
+            #[cfg(feature = "termcolor")]
+            target_style: Style::new(),
+            #[cfg(feature = "termcolor")]
+            location_hyperlink: None,
+            #[cfg(feature = "termcolor")]
+            highlights: Vec::new(),
+            #[cfg(feature = "console-attention")]
+            error_attention: ErrorAttention::default(),
+            #[cfg(feature = "console-attention")]
+            error_counter: RecordCounter::default(),
 
             #[cfg(feature = "paris")]
             enable_paris_formatting: true,
             line_ending: String::from("\u{000A}"),
+            strip_ansi: false,
+            multi_line_mode: MultiLineMode::Off,
+            header_alignment: HeaderAlignment::Off,
+            header_width: HeaderWidth::default(),
+            escape_control_chars: false,
         }
     }
 }
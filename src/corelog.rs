@@ -0,0 +1,171 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the CoreLogger Implementation, which does not require the standard library
+
+use core::cell::UnsafeCell;
+use core::fmt::{Result, Write};
+use core::sync::atomic::{AtomicBool, Ordering};
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+/// The CoreLogger struct. Provides a minimal Logger implementation for `core::fmt::Write`,
+/// e.g. a serial port of a microcontroller.
+///
+/// It does not depend on the standard library, threads or an allocator, so it is available
+/// with `default-features = false` (without the `std` feature). Records are printed
+/// in the format of the other loggers: `1.234 [INFO] target: your_message`.
+/// The time is taken from an optional function returning the milliseconds since boot.
+///
+/// Records logged while another record is written (e.g. from an interrupt handler)
+/// are dropped instead of waiting for the writer.
+///
+/// # Examples
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// struct Serial;
+///
+/// impl core::fmt::Write for Serial {
+///     fn write_str(&mut self, s: &str) -> core::fmt::Result {
+///         // hand the bytes to the hardware
+///         Ok(())
+///     }
+/// }
+///
+/// fn uptime_ms() -> u64 {
+///     // read a hardware timer
+///     1234
+/// }
+///
+/// static LOGGER: CoreLogger<Serial> = CoreLogger::new(LevelFilter::Info, Serial)
+///     .with_timestamp(uptime_ms)
+///     .with_line_ending("\r\n");
+///
+/// # fn main() {
+/// let _ = LOGGER.init();
+/// # }
+/// ```
+pub struct CoreLogger<W: Write + Send> {
+    level: LevelFilter,
+    target: bool,
+    timestamp: Option<fn() -> u64>,
+    line_ending: &'static str,
+    locked: AtomicBool,
+    writer: UnsafeCell<W>,
+}
+
+// The writer is only accessed while holding `locked`
+unsafe impl<W: Write + Send> Sync for CoreLogger<W> {}
+
+impl<W: Write + Send> CoreLogger<W> {
+    /// Creates a new logger writing every record up to the given level into `writer`.
+    ///
+    /// The logger can be created in a `static`, as `init` requires a `'static` reference.
+    pub const fn new(log_level: LevelFilter, writer: W) -> CoreLogger<W> {
+        CoreLogger {
+            level: log_level,
+            target: true,
+            timestamp: None,
+            line_ending: "\n",
+            locked: AtomicBool::new(false),
+            writer: UnsafeCell::new(writer),
+        }
+    }
+
+    /// Prints the time returned by `timestamp` in milliseconds in front of every record (default is no time)
+    pub const fn with_timestamp(mut self, timestamp: fn() -> u64) -> CoreLogger<W> {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Set if the target of a record should be printed (default is true)
+    pub const fn with_target(mut self, target: bool) -> CoreLogger<W> {
+        self.target = target;
+        self
+    }
+
+    /// Set the line ending printed after every record (default is `\n`)
+    pub const fn with_line_ending(mut self, line_ending: &'static str) -> CoreLogger<W> {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// init function. Globally initializes the CoreLogger as the one and only used log facility.
+    ///
+    /// Fails if another Logger was already initialized.
+    pub fn init(&'static self) -> core::result::Result<(), SetLoggerError> {
+        log::set_logger(self)?;
+        log::set_max_level(self.level);
+        Ok(())
+    }
+
+    /// Consumes the logger, returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    fn write_record(&self, writer: &mut W, record: &Record<'_>) -> Result {
+        if let Some(timestamp) = self.timestamp {
+            let millis = timestamp();
+            write!(writer, "{}.{:03} ", millis / 1000, millis % 1000)?;
+        }
+        write!(writer, "[{}] ", record.level())?;
+        if self.target {
+            write!(writer, "{}: ", record.target())?;
+        }
+        write!(writer, "{}{}", record.args(), self.line_ending)
+    }
+}
+
+impl<W: Write + Send> Log for CoreLogger<W> {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata())
+            || self
+                .locked
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+        {
+            return;
+        }
+
+        // SAFETY: holding `locked` grants exclusive access to the writer
+        let writer = unsafe { &mut *self.writer.get() };
+        let _ = self.write_record(writer, record);
+        self.locked.store(false, Ordering::Release);
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use log::{Level, LevelFilter, Record};
+
+    #[test]
+    fn core_logger() {
+        use crate::CoreLogger;
+        use log::Log;
+
+        let logger = CoreLogger::new(LevelFilter::Info, String::new())
+            .with_timestamp(|| 61_042)
+            .with_line_ending("\r\n");
+        for level in [Level::Warn, Level::Debug] {
+            logger.log(
+                &Record::builder()
+                    .level(level)
+                    .target("firmware")
+                    .args(format_args!("msg"))
+                    .build(),
+            );
+        }
+        assert_eq!(logger.into_inner(), "61.042 [WARN] firmware: msg\r\n");
+    }
+}
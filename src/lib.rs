@@ -12,7 +12,14 @@
 //! - `TermLogger` (advanced terminal logger, that splits to stdout/err and has color support) (can be excluded on unsupported platforms)
 //! - `WriteLogger` (logs to a given struct implementing `Write`, e.g. a file)
 //! - `CombinedLogger` (can be used to form combinations of the above loggers)
-//! - `TestLogger` (specialized logger for tests. Uses print!() / println!() for tests to be able to capture the output)
+//! - `SplitLogger` (routes records to different loggers by their level)
+//! - `TestLogger` (specialized logger for tests. Uses print!() / println!() for tests to be able to capture the output, or mirrors the records into a buffer for assertions, also process-wide)
+//! - `EarlyLogger` (buffers records logged before any of the above loggers is initialized)
+//! - `ProxyLogger` (forwards to a logger, that can be exchanged at any time)
+//! - `HeartbeatLogger` (periodically logs a heartbeat with the number of records logged since the previous one)
+//! - `BudgetLogger` (sheds debug and trace records once logging takes more time per second than budgeted)
+//! - `TracingLayer` (renders `tracing` events through any of the above loggers) (requires the `tracing` feature)
+//! - `CoreLogger` (minimal logger for `core::fmt::Write`, e.g. on embedded devices) (the only logger available without the `std` feature)
 //!
 //! Only one Logger should be initialized of the start of your program
 //! through the `Logger::init(...)` method. For the actual calling syntax
@@ -20,27 +27,94 @@
 //!
 
 #![deny(missing_docs, rust_2018_idioms)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "std")]
+mod background;
+#[cfg(feature = "std")]
+mod clock;
+#[cfg(feature = "std")]
 mod config;
+mod corelog;
+#[cfg(feature = "std")]
 mod loggers;
-
+#[cfg(feature = "std")]
+mod panic;
+#[cfg(feature = "std")]
+mod record;
+#[cfg(feature = "std")]
+mod scope;
+#[cfg(all(unix, feature = "signals"))]
+mod signal;
+#[cfg(feature = "std")]
+mod writers;
+
+#[cfg(feature = "std")]
+pub use self::clock::{Clock, FixedClock, SteppingClock, SystemClock};
+#[cfg(feature = "console-attention")]
+pub use self::config::ErrorAttention;
+#[cfg(feature = "std")]
 pub use self::config::{
-    format_description, Config, ConfigBuilder, FormatItem, LevelPadding, TargetPadding,
-    ThreadLogMode, ThreadPadding,
+    format_description, Config, ConfigBuilder, ConfigError, FormatItem, HeaderAlignment,
+    InvalidFormatDescription, LevelPadding, LineEnding, MultiLineMode, SequenceMode,
+    TargetAbbreviation, TargetPadding, TargetTruncation, ThreadLogMode, ThreadPadding,
+    UnixPrecision,
+};
+#[cfg(feature = "termcolor")]
+pub use self::config::{Style, Theme};
+pub use self::corelog::CoreLogger;
+#[cfg(feature = "tracing")]
+pub use self::loggers::TracingLayer;
+#[cfg(feature = "std")]
+pub use self::loggers::{
+    BudgetLogger, CombinedChild, CombinedLevelHandle, CombinedLogger, EarlyLogger, FanOutLogger,
+    HeartbeatLogger, ProxyLogger, SimpleLogger, SplitLogger, WriteLogger,
 };
 #[cfg(feature = "test")]
-pub use self::loggers::TestLogger;
-pub use self::loggers::{CombinedLogger, SimpleLogger, WriteLogger};
+pub use self::loggers::{CapturedRecords, TestLogger};
 #[cfg(feature = "termcolor")]
 pub use self::loggers::{TermLogger, TerminalMode};
+#[cfg(feature = "std")]
+pub use self::panic::install_panic_hook;
+#[cfg(feature = "std")]
+pub use self::record::LogRecordOwned;
+#[cfg(feature = "std")]
+pub use self::scope::{scope, Scope};
+#[cfg(all(unix, feature = "signals"))]
+pub use self::signal::{
+    install_signal_level_control, install_signal_level_control_with_handle, SignalLevelControl,
+};
+#[cfg(feature = "file-lock")]
+pub use self::writers::LockedFile;
+#[cfg(feature = "std")]
+pub use self::writers::SharedWriter;
+#[cfg(all(feature = "std", any(unix, windows)))]
+pub use self::writers::SocketWriter;
+#[cfg(feature = "encryption")]
+pub use self::writers::{decrypt_log, EncryptedWriter};
+#[cfg(feature = "audit")]
+pub use self::writers::{verify_log, verify_log_head, HashChainWriter};
+#[cfg(feature = "regex")]
+pub use regex::Regex;
 #[cfg(feature = "termcolor")]
 pub use termcolor::{Color, ColorChoice};
 
 pub use log::{Level, LevelFilter};
 
-use log::Log;
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 use log::*;
+#[cfg(feature = "std")]
+use log::{Log, SetLoggerError};
+#[cfg(feature = "std")]
+use std::any::Any;
+#[cfg(feature = "std")]
+use std::fmt;
+#[cfg(feature = "std")]
+use std::fs::OpenOptions;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
 
 #[cfg(feature = "paris")]
 #[doc(hidden)]
@@ -48,12 +122,199 @@ pub mod __private {
     pub use paris;
 }
 
+/// Globally initializes a logger fitting the environment of the program.
+///
+/// If both stdout and stderr are connected to a terminal, a colored `TermLogger` in
+/// `TerminalMode::Mixed` is used. Otherwise (e.g. when the output is piped into a file)
+/// or if the `termcolor` feature is disabled, a plain `SimpleLogger` is used.
+///
+/// Fails if another Logger was already initialized.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # fn main() {
+/// let _ = simplelog::init_auto(LevelFilter::Info, Config::default());
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn init_auto(log_level: LevelFilter, config: Config) -> Result<(), SetLoggerError> {
+    #[cfg(feature = "termcolor")]
+    {
+        use std::io::IsTerminal;
+
+        if std::io::stdout().is_terminal() && std::io::stderr().is_terminal() {
+            return TermLogger::init(log_level, config, TerminalMode::Mixed, ColorChoice::Auto);
+        }
+    }
+
+    SimpleLogger::init(log_level, config)
+}
+
+/// Globally initializes a logger writing to the terminal and appending to a file.
+///
+/// Records up to `term_level` are printed to the terminal with `Config::default()`, using a colored
+/// `TermLogger` (or a `SimpleLogger` without the `termcolor` feature). Records up to `file_level` are
+/// appended to the file at `path` using `Config::server()`, which always logs the UTC time, level,
+/// thread and target.
+///
+/// Fails if the file cannot be opened or another Logger was already initialized.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # fn main() {
+/// simplelog::init_term_and_file(LevelFilter::Warn, LevelFilter::Info, "my_rust_binary.log").unwrap();
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn init_term_and_file<P: AsRef<Path>>(
+    term_level: LevelFilter,
+    file_level: LevelFilter,
+    path: P,
+) -> Result<(), InitError> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    CombinedLogger::init(vec![
+        #[cfg(feature = "termcolor")]
+        TermLogger::new(
+            term_level,
+            Config::default(),
+            TerminalMode::Mixed,
+            ColorChoice::Auto,
+        ),
+        #[cfg(not(feature = "termcolor"))]
+        SimpleLogger::new(term_level, Config::default()),
+        WriteLogger::new(file_level, Config::server(), file),
+    ])?;
+    Ok(())
+}
+
+/// Error returned by initialization functions, that also open a file or spawn a thread, and by `try_init`
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InitError {
+    /// The log file could not be opened
+    Io(io::Error),
+    /// Another logger was already initialized
+    SetLogger(SetLoggerError),
+    /// A background thread of the logger could not be spawned
+    Spawn(io::Error),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitError::Io(err) => write!(f, "Failed to open the log file: {}", err),
+            InitError::SetLogger(err) => write!(f, "{}", err),
+            InitError::Spawn(err) => write!(f, "Failed to spawn the logging thread: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InitError::Io(err) => Some(err),
+            InitError::SetLogger(err) => Some(err),
+            InitError::Spawn(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for InitError {
+    fn from(err: io::Error) -> Self {
+        InitError::Io(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<SetLoggerError> for InitError {
+    fn from(err: SetLoggerError) -> Self {
+        InitError::SetLogger(err)
+    }
+}
+
+/// Maps the number of `-v` and `-q` flags of a command line tool to a level.
+///
+/// Without any flags warnings and errors are logged. Every `-v` enables the next
+/// more verbose level up to `Trace`, every `-q` the next less verbose one down to `Off`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # fn main() {
+/// assert_eq!(level_from_verbosity(0, 0), LevelFilter::Warn);
+/// assert_eq!(level_from_verbosity(2, 0), LevelFilter::Debug);
+/// assert_eq!(level_from_verbosity(9, 0), LevelFilter::Trace);
+/// assert_eq!(level_from_verbosity(0, 1), LevelFilter::Error);
+/// assert_eq!(level_from_verbosity(1, 3), LevelFilter::Off);
+/// # }
+/// ```
+pub fn level_from_verbosity(verbose: u8, quiet: u8) -> LevelFilter {
+    const LEVELS: [LevelFilter; 6] = [
+        LevelFilter::Off,
+        LevelFilter::Error,
+        LevelFilter::Warn,
+        LevelFilter::Info,
+        LevelFilter::Debug,
+        LevelFilter::Trace,
+    ];
+    let index = (LevelFilter::Warn as i32 + i32::from(verbose) - i32::from(quiet))
+        .clamp(0, LEVELS.len() as i32 - 1);
+    LEVELS[index as usize]
+}
+
+/// Flushes the globally installed logger and disables any further logging.
+///
+/// The `log` crate never drops the global logger, so buffered output may be lost,
+/// if the program exits without flushing. Call this function at the end of `main`
+/// (or before calling `std::process::exit`) to end logging deterministically.
+///
+/// All loggers of a `CombinedLogger` are flushed as well and background threads,
+/// like the ones of a `HeartbeatLogger` or `install_signal_level_control`, are stopped.
+/// Records logged after calling this function are discarded.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # struct Serial;
+/// # impl core::fmt::Write for Serial {
+/// #     fn write_str(&mut self, _: &str) -> core::fmt::Result { Ok(()) }
+/// # }
+/// static LOGGER: CoreLogger<Serial> = CoreLogger::new(LevelFilter::Info, Serial);
+///
+/// # fn main() {
+/// let _ = LOGGER.init();
+/// // ...
+/// simplelog::shutdown();
+/// # }
+/// ```
+pub fn shutdown() {
+    #[cfg(feature = "std")]
+    background::stop_all();
+    log::logger().flush();
+    log::set_max_level(LevelFilter::Off);
+}
+
 /// Trait to have a common interface to obtain the Level of Loggers
 ///
 /// Necessary for CombinedLogger to calculate
 /// the lowest used Level.
 ///
-pub trait SharedLogger: Log {
+#[cfg(feature = "std")]
+pub trait SharedLogger: Log + AsAny {
     /// Returns the set Level for this Logger
     ///
     /// # Examples
@@ -86,9 +347,94 @@ pub trait SharedLogger: Log {
 
     /// Returns the logger as a Log trait object
     fn as_log(self: Box<Self>) -> Box<dyn Log>;
+
+    /// Returns the name of the logger, e.g. `TermLogger`
+    ///
+    /// Used to describe the active loggers, e.g. in the startup banner.
+    /// Defaults to `custom`.
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+
+    /// Returns a description of where the logger writes its records to, e.g. `stdout` or a file path
+    ///
+    /// Used to report the active configuration at runtime. Defaults to `unknown`.
+    fn sink_description(&self) -> String {
+        String::from("unknown")
+    }
+
+    /// Returns the number of records the logger has written so far
+    ///
+    /// Records dropped by the level or the filters of the logger are not counted.
+    /// Defaults to the number counted by the `Config` of the logger, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let logger = WriteLogger::new(LevelFilter::Info, Config::default(), Vec::new());
+    /// println!("{} to {}: {:?} records", logger.name(), logger.sink_description(), logger.records_written());
+    /// # }
+    /// ```
+    fn records_written(&self) -> Option<u64> {
+        self.config().map(Config::records_written)
+    }
+}
+
+/// Trait to access a `SharedLogger` as `Any`, to downcast it to its concrete type
+///
+/// Implemented for every `SharedLogger`, custom implementations don't need to do anything.
+#[cfg(feature = "std")]
+pub trait AsAny {
+    /// Returns the logger as `Any`
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the logger as mutable `Any`
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+#[cfg(feature = "std")]
+impl<T: SharedLogger + 'static> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl dyn SharedLogger {
+    /// Returns the logger as the concrete type `T`, if it is one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let combined = CombinedLogger::new(vec![
+    ///     SimpleLogger::new(LevelFilter::Warn, Config::default()),
+    ///     WriteLogger::new(LevelFilter::Info, Config::default(), Vec::new()),
+    /// ]);
+    /// let write_logger = combined.loggers()[1].downcast_ref::<WriteLogger<Vec<u8>>>();
+    /// assert!(write_logger.is_some());
+    /// # }
+    /// ```
+    pub fn downcast_ref<T: SharedLogger + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Returns the logger as the mutable concrete type `T`, if it is one
+    pub fn downcast_mut<T: SharedLogger + 'static>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use std::fs::File;
     use std::io::Read;
@@ -115,7 +461,7 @@ mod tests {
                 File::create("thread_naming.log").unwrap(),
             ) as Box<dyn SharedLogger>);
 
-            for elem in vec![
+            for elem in [
                 LevelFilter::Off,
                 LevelFilter::Trace,
                 LevelFilter::Debug,
@@ -145,7 +491,7 @@ mod tests {
                 vec.push(WriteLogger::new(
                     LevelFilter::Error,
                     conf.clone(),
-                    File::create(&format!("error_{}.log", i)).unwrap(),
+                    File::create(format!("error_{}.log", i)).unwrap(),
                 ) as Box<dyn SharedLogger>);
                 #[cfg(feature = "test")]
                 vec.push(TestLogger::new(LevelFilter::Error, conf.clone()));
@@ -164,7 +510,7 @@ mod tests {
                 vec.push(WriteLogger::new(
                     LevelFilter::Warn,
                     conf.clone(),
-                    File::create(&format!("warn_{}.log", i)).unwrap(),
+                    File::create(format!("warn_{}.log", i)).unwrap(),
                 ) as Box<dyn SharedLogger>);
                 #[cfg(feature = "test")]
                 vec.push(TestLogger::new(LevelFilter::Warn, conf.clone()));
@@ -183,7 +529,7 @@ mod tests {
                 vec.push(WriteLogger::new(
                     LevelFilter::Info,
                     conf.clone(),
-                    File::create(&format!("info_{}.log", i)).unwrap(),
+                    File::create(format!("info_{}.log", i)).unwrap(),
                 ) as Box<dyn SharedLogger>);
                 #[cfg(feature = "test")]
                 vec.push(TestLogger::new(LevelFilter::Info, conf.clone()));
@@ -202,7 +548,7 @@ mod tests {
                 vec.push(WriteLogger::new(
                     LevelFilter::Debug,
                     conf.clone(),
-                    File::create(&format!("debug_{}.log", i)).unwrap(),
+                    File::create(format!("debug_{}.log", i)).unwrap(),
                 ) as Box<dyn SharedLogger>);
                 #[cfg(feature = "test")]
                 vec.push(TestLogger::new(LevelFilter::Debug, conf.clone()));
@@ -221,7 +567,7 @@ mod tests {
                 vec.push(WriteLogger::new(
                     LevelFilter::Trace,
                     conf.clone(),
-                    File::create(&format!("trace_{}.log", i)).unwrap(),
+                    File::create(format!("trace_{}.log", i)).unwrap(),
                 ) as Box<dyn SharedLogger>);
                 #[cfg(feature = "test")]
                 vec.push(TestLogger::new(LevelFilter::Trace, conf.clone()));
@@ -249,27 +595,27 @@ mod tests {
 
         for j in 1..i {
             let mut error = String::new();
-            File::open(&format!("error_{}.log", j))
+            File::open(format!("error_{}.log", j))
                 .unwrap()
                 .read_to_string(&mut error)
                 .unwrap();
             let mut warn = String::new();
-            File::open(&format!("warn_{}.log", j))
+            File::open(format!("warn_{}.log", j))
                 .unwrap()
                 .read_to_string(&mut warn)
                 .unwrap();
             let mut info = String::new();
-            File::open(&format!("info_{}.log", j))
+            File::open(format!("info_{}.log", j))
                 .unwrap()
                 .read_to_string(&mut info)
                 .unwrap();
             let mut debug = String::new();
-            File::open(&format!("debug_{}.log", j))
+            File::open(format!("debug_{}.log", j))
                 .unwrap()
                 .read_to_string(&mut debug)
                 .unwrap();
             let mut trace = String::new();
-            File::open(&format!("trace_{}.log", j))
+            File::open(format!("trace_{}.log", j))
                 .unwrap()
                 .read_to_string(&mut trace)
                 .unwrap();
@@ -0,0 +1,234 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the BudgetLogger Implementation
+
+use super::install::{set_global_logger, tolerate_existing_logger};
+use crate::{Config, InitError, SharedLogger};
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Length of the windows the budget applies to
+const WINDOW: Duration = Duration::from_secs(1);
+
+struct Window {
+    start: Instant,
+    spent: Duration,
+    shed: u64,
+}
+
+/// The BudgetLogger struct. Wraps a logger, limiting the time spent logging through it.
+///
+/// The time spent inside the wrapped logger is measured per second. Once it exceeds the
+/// budget, `Debug` and `Trace` records are shed for the rest of that second, so an
+/// accidental flood of verbose records cannot ruin the latency of a service.
+/// More severe records are always logged.
+///
+/// The number of shed records is logged with the target `budget` at `Level::Warn`
+/// together with the next record after the second is over:
+/// `00:00:00 [WARN] shed 1234 debug and trace records exceeding the logging budget of 5ms per second`
+pub struct BudgetLogger {
+    budget: Duration,
+    logger: Box<dyn SharedLogger>,
+    window: Mutex<Window>,
+}
+
+impl BudgetLogger {
+    /// init function. Globally initializes the BudgetLogger as the one and only used log facility.
+    ///
+    /// Takes the time per second that may be spent logging and the wrapped logger as arguments.
+    /// Fails if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::time::Duration;
+    /// # fn main() {
+    /// let _ = BudgetLogger::init(
+    ///     Duration::from_millis(5),
+    ///     SimpleLogger::new(LevelFilter::Trace, Config::default()),
+    /// );
+    /// # }
+    /// ```
+    pub fn init(budget: Duration, logger: Box<dyn SharedLogger>) -> Result<(), SetLoggerError> {
+        set_global_logger(BudgetLogger::new(budget, logger))
+    }
+
+    /// Like `init`, but tolerates an already initialized Logger.
+    ///
+    /// Returns `Ok(true)`, if the BudgetLogger was set as the global log facility,
+    /// and `Ok(false)`, if another Logger was already initialized.
+    pub fn try_init(budget: Duration, logger: Box<dyn SharedLogger>) -> Result<bool, InitError> {
+        tolerate_existing_logger(BudgetLogger::init(budget, logger))
+    }
+
+    /// allows to create a new logger, that can be independently used, no matter what is globally set.
+    ///
+    /// no macros are provided for this case and you probably
+    /// dont want to use this function, but `init()`, if you dont want to build a `CombinedLogger`.
+    ///
+    /// Takes the time per second that may be spent logging and the wrapped logger as arguments.
+    #[must_use]
+    pub fn new(budget: Duration, logger: Box<dyn SharedLogger>) -> Box<BudgetLogger> {
+        Box::new(BudgetLogger {
+            budget,
+            logger,
+            window: Mutex::new(Window {
+                start: Instant::now(),
+                spent: Duration::ZERO,
+                shed: 0,
+            }),
+        })
+    }
+
+    /// Returns the wrapped logger
+    pub fn logger(&self) -> &dyn SharedLogger {
+        &*self.logger
+    }
+
+    fn timed_log(&self, record: &Record<'_>) {
+        let start = Instant::now();
+        self.logger.log(record);
+        self.window.lock().unwrap().spent += start.elapsed();
+    }
+}
+
+impl Log for BudgetLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.logger.enabled(record.metadata()) {
+            return;
+        }
+
+        let shed = {
+            let mut window = self.window.lock().unwrap();
+            let now = Instant::now();
+            let shed = if now.duration_since(window.start) >= WINDOW {
+                let shed = window.shed;
+                *window = Window {
+                    start: now,
+                    spent: Duration::ZERO,
+                    shed: 0,
+                };
+                shed
+            } else {
+                0
+            };
+
+            if record.level() >= Level::Debug && window.spent > self.budget {
+                window.shed += 1;
+                return;
+            }
+            shed
+        };
+
+        if shed > 0 {
+            self.timed_log(
+                &Record::builder()
+                    .level(Level::Warn)
+                    .target("budget")
+                    .args(format_args!(
+                        "shed {} debug and trace records exceeding the logging budget of {:?} per second",
+                        shed, self.budget
+                    ))
+                    .build(),
+            );
+        }
+        self.timed_log(record);
+    }
+
+    fn flush(&self) {
+        self.logger.flush();
+    }
+}
+
+impl SharedLogger for BudgetLogger {
+    fn level(&self) -> LevelFilter {
+        self.logger.level()
+    }
+
+    fn config(&self) -> Option<&Config> {
+        self.logger.config()
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn Log> {
+        Box::new(*self)
+    }
+
+    fn name(&self) -> &'static str {
+        "BudgetLogger"
+    }
+
+    fn sink_description(&self) -> String {
+        self.logger.sink_description()
+    }
+
+    fn records_written(&self) -> Option<u64> {
+        self.logger.records_written()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ConfigBuilder;
+    use log::{Level, LevelFilter, Record};
+    use std::time::Duration;
+
+    #[test]
+    fn budget() {
+        use crate::{BudgetLogger, WriteLogger};
+        use log::Log;
+        use std::thread;
+
+        let logger = BudgetLogger::new(
+            Duration::ZERO,
+            WriteLogger::new(
+                LevelFilter::Trace,
+                ConfigBuilder::new()
+                    .set_time_level(LevelFilter::Off)
+                    .set_thread_level(LevelFilter::Off)
+                    .set_target_level(LevelFilter::Off)
+                    .build(),
+                Vec::new(),
+            ),
+        );
+        let log = |level: Level| {
+            logger.log(
+                &Record::builder()
+                    .level(level)
+                    .args(format_args!("{}", level))
+                    .build(),
+            )
+        };
+        // the first record exhausts the budget
+        log(Level::Debug);
+        log(Level::Trace);
+        log(Level::Debug);
+        log(Level::Error);
+        thread::sleep(Duration::from_millis(1100));
+        log(Level::Debug);
+
+        let output = logger
+            .logger()
+            .as_any()
+            .downcast_ref::<WriteLogger<Vec<u8>>>()
+            .unwrap()
+            .with_writer(|w| String::from_utf8(w.clone()).unwrap());
+        assert_eq!(
+            output,
+            "[DEBUG] DEBUG\n\
+             [ERROR] ERROR\n\
+             [WARN] shed 2 debug and trace records exceeding the logging budget of 0ns per second\n\
+             [DEBUG] DEBUG\n"
+        );
+    }
+}
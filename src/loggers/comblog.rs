@@ -7,15 +7,167 @@
 
 //! Module providing the CombinedLogger Implementation
 
-use crate::{Config, SharedLogger};
-use log::{set_boxed_logger, set_max_level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use super::install::{set_global_logger, tolerate_existing_logger, try_set_global_logger};
+use super::logging::filter_matches;
+use crate::{Config, InitError, SharedLogger};
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::Off,
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+/// Levels of a CombinedLogger, shared with its `CombinedLevelHandle`s
+struct Levels {
+    combined: AtomicUsize,
+    /// Levels of the loggers followed by the levels of the routes
+    children: Vec<AtomicUsize>,
+    /// Maximum levels of the loggers followed by the routes, the levels cannot be raised above
+    max: Vec<LevelFilter>,
+    routes: Vec<String>,
+    global: AtomicBool,
+}
+
+impl Levels {
+    fn child(&self, index: usize) -> LevelFilter {
+        LEVELS[self.children[index].load(Ordering::Relaxed)]
+    }
+
+    fn enabled(&self, index: usize, level: Level) -> bool {
+        level <= self.child(index)
+    }
+
+    fn index(&self, child: CombinedChild<'_>) -> Option<usize> {
+        match child {
+            CombinedChild::Logger(index) => {
+                Some(index).filter(|&index| index < self.max.len() - self.routes.len())
+            }
+            CombinedChild::Route(target) => self
+                .routes
+                .iter()
+                .position(|route| route == target)
+                .map(|index| self.max.len() - self.routes.len() + index),
+        }
+    }
+}
+
+/// A logger of a [`CombinedLogger`], either the index of a logger or the target of a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinedChild<'a> {
+    /// The logger at this index of the loggers
+    Logger(usize),
+    /// The logger of the route with this target
+    Route(&'a str),
+}
+
+impl From<usize> for CombinedChild<'_> {
+    fn from(index: usize) -> Self {
+        CombinedChild::Logger(index)
+    }
+}
+
+impl<'a> From<&'a str> for CombinedChild<'a> {
+    fn from(target: &'a str) -> Self {
+        CombinedChild::Route(target)
+    }
+}
+
+/// Handle to change the levels of the loggers of a [`CombinedLogger`], while it is in use.
+///
+/// Obtained through [`CombinedLogger::init_with_handle`] or [`CombinedLogger::level_handle`].
+/// A level can be lowered and raised again, but never above the level the logger was created with.
+/// Create the loggers with the most verbose level needed and lower them right away instead.
+///
+/// If the CombinedLogger was initialized through `init_with_handle`, the global maximum
+/// log level is adjusted as well.
+#[derive(Clone)]
+pub struct CombinedLevelHandle {
+    levels: Arc<Levels>,
+}
+
+impl CombinedLevelHandle {
+    /// Sets the level of a logger, given by its index or the target of its route.
+    ///
+    /// Returns the resulting level, which is limited by the level the logger was created with,
+    /// or `None`, if there is no such logger.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::fs::File;
+    /// # fn main() {
+    /// let combined_logger = CombinedLogger::new(vec![
+    ///     SimpleLogger::new(LevelFilter::Debug, Config::default()),
+    ///     WriteLogger::new(LevelFilter::Debug, Config::default(), File::create("my_rust_bin.log").unwrap()),
+    /// ]);
+    /// let handle = combined_logger.level_handle();
+    /// // warnings to the terminal only, until troubleshooting requires more
+    /// handle.set_child_level(0, LevelFilter::Warn);
+    /// assert_eq!(handle.set_child_level(0, LevelFilter::Trace), Some(LevelFilter::Debug));
+    /// # }
+    /// ```
+    pub fn set_child_level<'a, C: Into<CombinedChild<'a>>>(
+        &self,
+        child: C,
+        level: LevelFilter,
+    ) -> Option<LevelFilter> {
+        let index = self.levels.index(child.into())?;
+        let level = level.min(self.levels.max[index]);
+        self.levels.children[index].store(level as usize, Ordering::Relaxed);
+        self.update_combined();
+        Some(level)
+    }
+
+    /// Changes the levels of all loggers through `step`, limited by the levels they were created with.
+    ///
+    /// Returns the resulting level of the CombinedLogger.
+    #[cfg(all(unix, feature = "signals"))]
+    pub(crate) fn step_levels(&self, step: fn(LevelFilter) -> LevelFilter) -> LevelFilter {
+        for (index, max) in self.levels.max.iter().enumerate() {
+            let level = step(self.levels.child(index)).min(*max);
+            self.levels.children[index].store(level as usize, Ordering::Relaxed);
+        }
+        self.update_combined()
+    }
+
+    fn update_combined(&self) -> LevelFilter {
+        let combined = (0..self.levels.children.len())
+            .map(|index| self.levels.child(index))
+            .max()
+            .unwrap_or(LevelFilter::Off);
+        self.levels
+            .combined
+            .store(combined as usize, Ordering::Relaxed);
+        if self.levels.global.load(Ordering::Relaxed) {
+            log::set_max_level(combined);
+        }
+        combined
+    }
+
+    /// Returns the current level of a logger, given by its index or the target of its route
+    pub fn child_level<'a, C: Into<CombinedChild<'a>>>(&self, child: C) -> Option<LevelFilter> {
+        let index = self.levels.index(child.into())?;
+        Some(self.levels.child(index))
+    }
+}
 
 /// The CombinedLogger struct. Provides a Logger implementation that proxies multiple Loggers as one.
 ///
 /// The purpose is to allow multiple Loggers to be set globally
+///
+/// Loggers can also be registered for specific targets through [`CombinedLogger::new_routed`],
+/// receiving the records of these targets instead of the other loggers.
 pub struct CombinedLogger {
-    level: LevelFilter,
+    levels: Arc<Levels>,
     logger: Vec<Box<dyn SharedLogger>>,
+    routes: Vec<(String, Box<dyn SharedLogger>)>,
 }
 
 impl CombinedLogger {
@@ -44,9 +196,81 @@ impl CombinedLogger {
     /// # }
     /// ```
     pub fn init(logger: Vec<Box<dyn SharedLogger>>) -> Result<(), SetLoggerError> {
-        let comblog = CombinedLogger::new(logger);
-        set_max_level(comblog.level());
-        set_boxed_logger(comblog)
+        set_global_logger(CombinedLogger::new(logger))
+    }
+
+    /// Like `init`, but hands the loggers back, if another Logger was already initialized.
+    ///
+    /// This allows to still use the loggers, e.g. by wrapping them into a local logger.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// # let _ = SimpleLogger::init(LevelFilter::Warn, Config::default());
+    /// let loggers: Vec<Box<dyn SharedLogger>> = vec![SimpleLogger::new(LevelFilter::Info, Config::default())];
+    /// if let Err((_, loggers)) = CombinedLogger::init_or_return(loggers) {
+    ///     let local_logger = CombinedLogger::new(loggers);
+    /// #   assert_eq!(local_logger.loggers().len(), 1);
+    /// }
+    /// # else { panic!("a logger was already set") }
+    /// # }
+    /// ```
+    pub fn init_or_return(
+        logger: Vec<Box<dyn SharedLogger>>,
+    ) -> Result<(), (SetLoggerError, Vec<Box<dyn SharedLogger>>)> {
+        try_set_global_logger(CombinedLogger::new(logger))
+            .map_err(|(err, combined)| (err, combined.logger))
+    }
+
+    /// Like `init`, but tolerates an already initialized Logger.
+    ///
+    /// Returns `Ok(true)`, if the CombinedLogger was set as the global log facility,
+    /// and `Ok(false)`, if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// match CombinedLogger::try_init(vec![SimpleLogger::new(LevelFilter::Info, Config::default())]) {
+    ///     Ok(true) => {}
+    ///     Ok(false) => println!("logging was already set up"),
+    ///     Err(err) => println!("failed to set up logging: {}", err),
+    /// }
+    /// # }
+    /// ```
+    pub fn try_init(logger: Vec<Box<dyn SharedLogger>>) -> Result<bool, InitError> {
+        tolerate_existing_logger(CombinedLogger::init(logger))
+    }
+
+    /// Like `init`, but returns a handle to change the levels of the loggers afterwards.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::fs::File;
+    /// # fn main() {
+    /// if let Ok(handle) = CombinedLogger::init_with_handle(vec![
+    ///     SimpleLogger::new(LevelFilter::Debug, Config::default()),
+    ///     WriteLogger::new(LevelFilter::Debug, Config::default(), File::create("my_rust_bin.log").unwrap()),
+    /// ]) {
+    ///     handle.set_child_level(0, LevelFilter::Warn);
+    ///     // later on, while troubleshooting
+    ///     handle.set_child_level(0, LevelFilter::Debug);
+    /// }
+    /// # }
+    /// ```
+    pub fn init_with_handle(
+        logger: Vec<Box<dyn SharedLogger>>,
+    ) -> Result<CombinedLevelHandle, SetLoggerError> {
+        let combined = CombinedLogger::new(logger);
+        let handle = combined.level_handle();
+        set_global_logger(combined)?;
+        handle.levels.global.store(true, Ordering::Relaxed);
+        Ok(handle)
     }
 
     /// allows to create a new logger, that can be independently used, no matter whats globally set.
@@ -76,35 +300,159 @@ impl CombinedLogger {
     /// ```
     #[must_use]
     pub fn new(logger: Vec<Box<dyn SharedLogger>>) -> Box<CombinedLogger> {
+        CombinedLogger::new_routed(logger, Vec::new())
+    }
+
+    /// init function. Globally initializes a routing CombinedLogger (see `new_routed`) as the one and only used log facility.
+    ///
+    /// Fails if another logger is already set globally.
+    pub fn init_routed(
+        logger: Vec<Box<dyn SharedLogger>>,
+        routes: Vec<(&str, Box<dyn SharedLogger>)>,
+    ) -> Result<(), SetLoggerError> {
+        set_global_logger(CombinedLogger::new_routed(logger, routes))
+    }
+
+    /// Like `new`, but records of targets matching a route only go to the logger of that route.
+    ///
+    /// Routes are matched like allow and ignore filters of the `Config` (see `ConfigBuilder::add_filter_allow`)
+    /// in the given order, the first matching one receives the record.
+    /// Records not matching any route go to all of the other loggers.
+    /// Use a `CombinedLogger` as the logger of a route to send its records to multiple loggers.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::fs::File;
+    /// # fn main() {
+    /// let combined_logger = CombinedLogger::new_routed(
+    ///             vec![SimpleLogger::new(LevelFilter::Info, Config::default())],
+    ///             vec![
+    ///                 ("audit::*", WriteLogger::new(LevelFilter::Info, Config::default(), File::create("my_rust_bin.log").unwrap())),
+    ///             ]
+    ///         );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new_routed(
+        logger: Vec<Box<dyn SharedLogger>>,
+        routes: Vec<(&str, Box<dyn SharedLogger>)>,
+    ) -> Box<CombinedLogger> {
+        let routes: Vec<_> = routes
+            .into_iter()
+            .map(|(target, logger)| (target.to_string(), logger))
+            .collect();
+
         let mut log_level = LevelFilter::Off;
-        for log in &logger {
+        for log in logger.iter().chain(routes.iter().map(|(_, log)| log)) {
             if log_level < log.level() {
                 log_level = log.level();
             }
         }
 
+        let max: Vec<_> = logger
+            .iter()
+            .chain(routes.iter().map(|(_, log)| log))
+            .map(|log| log.level())
+            .collect();
+        let levels = Levels {
+            combined: AtomicUsize::new(log_level as usize),
+            children: max
+                .iter()
+                .map(|&level| AtomicUsize::new(level as usize))
+                .collect(),
+            max,
+            routes: routes.iter().map(|(target, _)| target.clone()).collect(),
+            global: AtomicBool::new(false),
+        };
+
         Box::new(CombinedLogger {
-            level: log_level,
+            levels: Arc::new(levels),
             logger,
+            routes,
         })
     }
+
+    /// Returns a handle to change the levels of the loggers, see [`CombinedLevelHandle`]
+    pub fn level_handle(&self) -> CombinedLevelHandle {
+        CombinedLevelHandle {
+            levels: self.levels.clone(),
+        }
+    }
+
+    fn level(&self) -> LevelFilter {
+        LEVELS[self.levels.combined.load(Ordering::Relaxed)]
+    }
+
+    /// Returns the index (among all levels) and logger of the first route matching the target
+    fn route(&self, target: &str) -> Option<(usize, &dyn SharedLogger)> {
+        self.routes
+            .iter()
+            .position(|(route, _)| filter_matches(route, target))
+            .map(|index| (self.logger.len() + index, &*self.routes[index].1))
+    }
+
+    /// Returns the combined loggers
+    ///
+    /// Use [`downcast_ref`](trait.SharedLogger.html#method.downcast_ref) to access a logger as its concrete type.
+    pub fn loggers(&self) -> &[Box<dyn SharedLogger>] {
+        &self.logger
+    }
+
+    /// Returns the combined loggers mutably
+    pub fn loggers_mut(&mut self) -> &mut [Box<dyn SharedLogger>] {
+        &mut self.logger
+    }
+
+    /// Returns the routes, each consisting of the matched target and its logger
+    pub fn routes(&self) -> &[(String, Box<dyn SharedLogger>)] {
+        &self.routes
+    }
 }
 
 impl Log for CombinedLogger {
     fn enabled(&self, metadata: &Metadata<'_>) -> bool {
-        metadata.level() <= self.level
+        if metadata.level() > self.level() {
+            return false;
+        }
+
+        match self.route(metadata.target()) {
+            Some((index, log)) => {
+                self.levels.enabled(index, metadata.level()) && log.enabled(metadata)
+            }
+            None => self.logger.iter().enumerate().any(|(index, log)| {
+                self.levels.enabled(index, metadata.level()) && log.enabled(metadata)
+            }),
+        }
     }
 
     fn log(&self, record: &Record<'_>) {
-        if self.enabled(record.metadata()) {
-            for log in &self.logger {
-                log.log(record);
+        // the loggers check their filters themselves
+        if record.level() <= self.level() {
+            match self.route(record.target()) {
+                Some((index, log)) => {
+                    if self.levels.enabled(index, record.level()) {
+                        log.log(record);
+                    }
+                }
+                None => {
+                    for (index, log) in self.logger.iter().enumerate() {
+                        if self.levels.enabled(index, record.level()) {
+                            log.log(record);
+                        }
+                    }
+                }
             }
         }
     }
 
     fn flush(&self) {
-        for log in &self.logger {
+        for log in self
+            .logger
+            .iter()
+            .chain(self.routes.iter().map(|(_, log)| log))
+        {
             log.flush();
         }
     }
@@ -112,7 +460,7 @@ impl Log for CombinedLogger {
 
 impl SharedLogger for CombinedLogger {
     fn level(&self) -> LevelFilter {
-        self.level
+        CombinedLogger::level(self)
     }
 
     fn config(&self) -> Option<&Config> {
@@ -122,4 +470,173 @@ impl SharedLogger for CombinedLogger {
     fn as_log(self: Box<Self>) -> Box<dyn Log> {
         Box::new(*self)
     }
+
+    fn name(&self) -> &'static str {
+        "CombinedLogger"
+    }
+
+    fn sink_description(&self) -> String {
+        self.logger
+            .iter()
+            .chain(self.routes.iter().map(|(_, logger)| logger))
+            .map(|logger| format!("{}: {}", logger.name(), logger.sink_description()))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    fn records_written(&self) -> Option<u64> {
+        self.logger
+            .iter()
+            .chain(self.routes.iter().map(|(_, logger)| logger))
+            .filter_map(|logger| logger.records_written())
+            .reduce(|a, b| a + b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Config, ConfigBuilder};
+    use log::{Level, LevelFilter, Record};
+
+    #[test]
+    fn routing() {
+        use crate::{CombinedLogger, SharedLogger, WriteLogger};
+        use log::Log;
+
+        let config = || {
+            ConfigBuilder::new()
+                .set_time_level(LevelFilter::Off)
+                .set_target_level(LevelFilter::Error)
+                .build()
+        };
+        let combined = CombinedLogger::new_routed(
+            vec![WriteLogger::new(LevelFilter::Info, config(), Vec::new())],
+            vec![(
+                "audit::*",
+                WriteLogger::new(LevelFilter::Info, config(), Vec::new()),
+            )],
+        );
+        for target in ["app", "audit::login"] {
+            combined.log(
+                &Record::builder()
+                    .level(Level::Info)
+                    .target(target)
+                    .args(format_args!("msg"))
+                    .build(),
+            );
+        }
+
+        let output = |logger: &dyn SharedLogger| {
+            logger
+                .as_any()
+                .downcast_ref::<WriteLogger<Vec<u8>>>()
+                .unwrap()
+                .with_writer(|w| String::from_utf8(w.clone()).unwrap())
+        };
+        assert_eq!(output(&*combined.loggers()[0]), "[INFO] app: msg\n");
+        assert_eq!(
+            output(&*combined.routes()[0].1),
+            "[INFO] audit::login: msg\n"
+        );
+    }
+
+    #[test]
+    fn combined_level_handle() {
+        use crate::{CombinedLogger, WriteLogger};
+        use log::Log;
+
+        let combined = CombinedLogger::new_routed(
+            vec![
+                WriteLogger::new(LevelFilter::Debug, Config::minimal(), Vec::new()),
+                WriteLogger::new(LevelFilter::Info, Config::minimal(), Vec::new()),
+            ],
+            vec![(
+                "audit",
+                WriteLogger::new(LevelFilter::Info, Config::minimal(), Vec::new()),
+            )],
+        );
+        let handle = combined.level_handle();
+        assert_eq!(
+            handle.set_child_level(0, LevelFilter::Warn),
+            Some(LevelFilter::Warn)
+        );
+        assert_eq!(combined.level(), LevelFilter::Info);
+        assert_eq!(
+            handle.set_child_level("audit", LevelFilter::Off),
+            Some(LevelFilter::Off)
+        );
+        assert_eq!(
+            handle.set_child_level(1, LevelFilter::Trace),
+            Some(LevelFilter::Info)
+        );
+        assert_eq!(handle.set_child_level(2, LevelFilter::Info), None);
+        assert_eq!(handle.child_level("other"), None);
+
+        for (level, target) in [
+            (Level::Info, "app"),
+            (Level::Warn, "app"),
+            (Level::Error, "audit"),
+        ] {
+            combined.log(
+                &Record::builder()
+                    .level(level)
+                    .target(target)
+                    .args(format_args!("{}", level))
+                    .build(),
+            );
+        }
+        let output = |index: usize| {
+            combined.loggers()[index]
+                .as_any()
+                .downcast_ref::<WriteLogger<Vec<u8>>>()
+                .unwrap()
+                .with_writer(|w| String::from_utf8(w.clone()).unwrap())
+        };
+        assert_eq!(output(0), "WARN\n");
+        assert_eq!(output(1), "INFO\nWARN\n");
+        assert!(combined.routes()[0]
+            .1
+            .as_any()
+            .downcast_ref::<WriteLogger<Vec<u8>>>()
+            .unwrap()
+            .with_writer(|w| w.is_empty()));
+    }
+
+    #[test]
+    fn introspection() {
+        use crate::{CombinedLogger, SharedLogger, WriteLogger};
+        use log::Log;
+
+        let combined = CombinedLogger::new(vec![
+            WriteLogger::new(LevelFilter::Info, Config::default(), Vec::new())
+                .with_sink_description("app.log"),
+            WriteLogger::new(
+                LevelFilter::Warn,
+                ConfigBuilder::new().add_filter_ignore_str("noisy").build(),
+                Vec::<u8>::new(),
+            )
+            .with_sink_description("warnings.log"),
+        ]);
+        for (level, target) in [
+            (Level::Info, "app"),
+            (Level::Warn, "app"),
+            (Level::Warn, "noisy"),
+        ] {
+            combined.log(
+                &Record::builder()
+                    .level(level)
+                    .target(target)
+                    .args(format_args!("{}", level))
+                    .build(),
+            );
+        }
+
+        assert_eq!(
+            combined.sink_description(),
+            "WriteLogger: app.log; WriteLogger: warnings.log"
+        );
+        assert_eq!(combined.loggers()[0].records_written(), Some(3));
+        assert_eq!(combined.loggers()[1].records_written(), Some(1));
+        assert_eq!(combined.records_written(), Some(4));
+    }
 }
@@ -0,0 +1,135 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the EarlyLogger Implementation
+
+use super::logging::start_time;
+use crate::{LogRecordOwned, SharedLogger};
+use log::{set_logger, set_max_level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::sync::RwLock;
+
+/// Maximum amount of records buffered before the real logger is initialized
+const BUFFER_LIMIT: usize = 10_000;
+
+static EARLY_LOGGER: EarlyLogger = EarlyLogger {
+    state: RwLock::new(State::Inactive),
+};
+
+enum State {
+    Inactive,
+    Buffering(LevelFilter, Vec<LogRecordOwned>),
+    Active(Box<dyn SharedLogger>),
+}
+
+/// The EarlyLogger struct. Buffers records until the actual logger is initialized.
+///
+/// Install it as early as possible at the start of your program. Every record logged
+/// afterwards is kept in memory (up to 10000 records, any further records are discarded),
+/// until one of the `init` functions of this crate (e.g. `CombinedLogger::init`) is called.
+/// The initialized logger then takes over and all buffered records are replayed into it.
+///
+/// Replayed records keep the time and thread they were originally logged at.
+/// Records logged by other threads during the replay may be printed before them.
+pub struct EarlyLogger {
+    state: RwLock<State>,
+}
+
+impl EarlyLogger {
+    /// init function. Globally initializes the EarlyLogger as the one and only used log facility.
+    ///
+    /// Takes the `Level` up to which records should be buffered as an argument.
+    /// Fails if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let _ = EarlyLogger::init(LevelFilter::Trace);
+    /// // parse arguments, load configuration, ...
+    /// let _ = SimpleLogger::init(LevelFilter::Info, Config::default());
+    /// # }
+    /// ```
+    pub fn init(log_level: LevelFilter) -> Result<(), SetLoggerError> {
+        let mut state = EARLY_LOGGER.state.write().unwrap();
+        set_logger(&EARLY_LOGGER)?;
+        *state = State::Buffering(log_level, Vec::new());
+        set_max_level(log_level);
+        start_time();
+        Ok(())
+    }
+
+    /// Hands the logger over to a buffering EarlyLogger and replays the buffered records.
+    ///
+    /// Returns the logger back, if the EarlyLogger is not installed or was already replaced.
+    fn replace<L: SharedLogger + 'static>(&self, logger: Box<L>) -> Result<(), Box<L>> {
+        let records = {
+            let mut state = self.state.write().unwrap();
+            let records = match &mut *state {
+                State::Buffering(_, records) => std::mem::take(records),
+                _ => return Err(logger),
+            };
+            *state = State::Active(logger);
+            records
+        };
+
+        // replay without holding the write lock, the logger might log records itself
+        if let State::Active(logger) = &*self.state.read().unwrap() {
+            for record in records {
+                record.replay(&**logger);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Log for EarlyLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        match &*self.state.read().unwrap() {
+            State::Inactive => false,
+            State::Buffering(level, _) => metadata.level() <= *level,
+            State::Active(logger) => logger.enabled(metadata),
+        }
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if let State::Active(logger) = &*self.state.read().unwrap() {
+            logger.log(record);
+            return;
+        }
+
+        match &mut *self.state.write().unwrap() {
+            State::Inactive => return,
+            State::Buffering(level, records) => {
+                if record.level() <= *level && records.len() < BUFFER_LIMIT {
+                    records.push(LogRecordOwned::capture(record));
+                }
+                return;
+            }
+            // replaced in the meantime
+            State::Active(_) => {}
+        }
+
+        // a replaced EarlyLogger never becomes inactive again
+        if let State::Active(logger) = &*self.state.read().unwrap() {
+            logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        if let State::Active(logger) = &*self.state.read().unwrap() {
+            logger.flush();
+        }
+    }
+}
+
+/// Hands the logger over to a buffering EarlyLogger, see `EarlyLogger::replace`.
+///
+/// Returns the logger back, if no EarlyLogger is buffering.
+pub(crate) fn take_over<L: SharedLogger + 'static>(logger: Box<L>) -> Result<(), Box<L>> {
+    EARLY_LOGGER.replace(logger)
+}
@@ -0,0 +1,176 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the FanOutLogger Implementation
+
+use super::install::{set_global_logger, tolerate_existing_logger};
+use super::logging::{should_skip_metadata, try_log, with_render_buffer};
+use crate::{Config, InitError, SharedLogger};
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::io::Write;
+use std::sync::Mutex;
+
+/// The FanOutLogger struct. Provides a Logger implementation writing every record to multiple `Write` sinks.
+///
+/// Unlike a `CombinedLogger` of several `WriteLogger`s sharing the same `Config`,
+/// every record is only filtered and formatted once and the resulting bytes are written to all sinks.
+pub struct FanOutLogger {
+    level: LevelFilter,
+    config: Config,
+    sinks: Vec<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl FanOutLogger {
+    /// init function. Globally initializes the FanOutLogger as the one and only used log facility.
+    ///
+    /// Takes the desired `Level`, `Config` and `Write` sinks as arguments. They cannot be changed later on.
+    /// Fails if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::fs::File;
+    /// # fn main() {
+    /// let _ = FanOutLogger::init(
+    ///     LevelFilter::Info,
+    ///     Config::default(),
+    ///     vec![
+    ///         Box::new(File::create("my_rust_bin.log").unwrap()),
+    ///         Box::new(std::io::stderr()),
+    ///     ],
+    /// );
+    /// # }
+    /// ```
+    pub fn init(
+        log_level: LevelFilter,
+        config: Config,
+        sinks: Vec<Box<dyn Write + Send>>,
+    ) -> Result<(), SetLoggerError> {
+        set_global_logger(FanOutLogger::new(log_level, config, sinks))
+    }
+
+    /// Like `init`, but tolerates an already initialized Logger.
+    ///
+    /// Returns `Ok(true)`, if the FanOutLogger was set as the global log facility,
+    /// and `Ok(false)`, if another Logger was already initialized.
+    pub fn try_init(
+        log_level: LevelFilter,
+        config: Config,
+        sinks: Vec<Box<dyn Write + Send>>,
+    ) -> Result<bool, InitError> {
+        tolerate_existing_logger(FanOutLogger::init(log_level, config, sinks))
+    }
+
+    /// allows to create a new logger, that can be independently used, no matter what is globally set.
+    ///
+    /// no macros are provided for this case and you probably
+    /// dont want to use this function, but `init()`, if you dont want to build a `CombinedLogger`.
+    ///
+    /// Takes the desired `Level`, `Config` and `Write` sinks as arguments. They cannot be changed later on.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let fan_out_logger = FanOutLogger::new(
+    ///     LevelFilter::Info,
+    ///     Config::default(),
+    ///     vec![Box::new(std::io::stdout()), Box::new(std::io::stderr())],
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new(
+        log_level: LevelFilter,
+        config: Config,
+        sinks: Vec<Box<dyn Write + Send>>,
+    ) -> Box<FanOutLogger> {
+        Box::new(FanOutLogger {
+            level: log_level,
+            config,
+            sinks: sinks.into_iter().map(Mutex::new).collect(),
+        })
+    }
+}
+
+impl Log for FanOutLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level && !should_skip_metadata(&self.config, metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        // the filters are checked while logging the record
+        if record.level() <= self.level {
+            with_render_buffer(|buffer| {
+                let _ = try_log(&self.config, record, buffer);
+                if !buffer.is_empty() {
+                    for sink in &self.sinks {
+                        let _ = sink.lock().unwrap().write_all(buffer);
+                    }
+                }
+            });
+        }
+    }
+
+    fn flush(&self) {
+        for sink in &self.sinks {
+            let _ = sink.lock().unwrap().flush();
+        }
+    }
+}
+
+impl SharedLogger for FanOutLogger {
+    fn level(&self) -> LevelFilter {
+        self.level
+    }
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn Log> {
+        Box::new(*self)
+    }
+
+    fn name(&self) -> &'static str {
+        "FanOutLogger"
+    }
+
+    fn sink_description(&self) -> String {
+        format!("{} sinks", self.sinks.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Config, FanOutLogger, SharedWriter};
+    use log::{Level, LevelFilter, Log, Record};
+
+    #[test]
+    fn fan_out_to_every_sink() {
+        let first = SharedWriter::new(Vec::new());
+        let second = SharedWriter::new(Vec::new());
+        let logger = FanOutLogger::new(
+            LevelFilter::Info,
+            Config::minimal(),
+            vec![Box::new(first.clone()), Box::new(second.clone())],
+        );
+        for level in &[Level::Warn, Level::Debug] {
+            logger.log(
+                &Record::builder()
+                    .level(*level)
+                    .args(format_args!("{}", level))
+                    .build(),
+            );
+        }
+
+        assert_eq!(&*first.lock(), b"WARN\n");
+        assert_eq!(&*second.lock(), b"WARN\n");
+    }
+}
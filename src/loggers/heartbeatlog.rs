@@ -0,0 +1,280 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the HeartbeatLogger Implementation
+
+use super::install::{set_global_logger, tolerate_existing_logger};
+use crate::background::BackgroundThread;
+use crate::{Config, InitError, SharedLogger};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+struct Inner {
+    logger: Box<dyn SharedLogger>,
+    level: Level,
+    message: String,
+    counts: [AtomicU64; 5],
+}
+
+impl Inner {
+    fn heartbeat(&self) {
+        let count = |level: Level| self.counts[level as usize - 1].swap(0, Ordering::Relaxed);
+        let (error, warn, info, debug, trace) = (
+            count(Level::Error),
+            count(Level::Warn),
+            count(Level::Info),
+            count(Level::Debug),
+            count(Level::Trace),
+        );
+        self.logger.log(
+            &Record::builder()
+                .level(self.level)
+                .target("heartbeat")
+                .args(format_args!(
+                    "{} (since the last heartbeat: {} errors, {} warnings, {} info, {} debug, {} trace)",
+                    self.message, error, warn, info, debug, trace
+                ))
+                .build(),
+        );
+        self.logger.flush();
+    }
+}
+
+/// The HeartbeatLogger struct. Wraps a logger, logging a heartbeat record through it periodically.
+///
+/// A background thread logs the heartbeat with the target `heartbeat` every `interval`,
+/// even if nothing else is logged, confirming that a quiet program is still alive.
+/// The heartbeat contains the number of records per level logged since the previous one:
+/// `00:00:00 [INFO] still alive (since the last heartbeat: 0 errors, 2 warnings, 15 info, 0 debug, 0 trace)`
+///
+/// Only records the wrapped logger is enabled for are counted. The heartbeat is logged
+/// through the wrapped logger as well, so its level needs to be enabled there and
+/// by `log::max_level`, like for records of the logging macros.
+/// The thread stops, once the HeartbeatLogger is dropped, `stop` or `simplelog::shutdown` is called.
+pub struct HeartbeatLogger {
+    inner: Arc<Inner>,
+    thread: BackgroundThread,
+}
+
+impl HeartbeatLogger {
+    /// init function. Globally initializes the HeartbeatLogger as the one and only used log facility.
+    ///
+    /// Takes the interval, level and message of the heartbeat as well as the wrapped logger as arguments.
+    /// Fails if another Logger was already initialized or the background thread could not be spawned.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::time::Duration;
+    /// # fn main() {
+    /// let _ = HeartbeatLogger::init(
+    ///     Duration::from_secs(300),
+    ///     Level::Info,
+    ///     "still alive",
+    ///     SimpleLogger::new(LevelFilter::Info, Config::default()),
+    /// );
+    /// # }
+    /// ```
+    pub fn init(
+        interval: Duration,
+        level: Level,
+        message: &str,
+        logger: Box<dyn SharedLogger>,
+    ) -> Result<(), InitError> {
+        let logger =
+            HeartbeatLogger::new(interval, level, message, logger).map_err(InitError::Spawn)?;
+        set_global_logger(logger)?;
+        Ok(())
+    }
+
+    /// Like `init`, but tolerates an already initialized Logger.
+    ///
+    /// Returns `Ok(true)`, if the HeartbeatLogger was set as the global log facility,
+    /// and `Ok(false)`, if another Logger was already initialized.
+    /// Failing to start the heartbeat thread is still returned as an error.
+    pub fn try_init(
+        interval: Duration,
+        level: Level,
+        message: &str,
+        logger: Box<dyn SharedLogger>,
+    ) -> Result<bool, InitError> {
+        tolerate_existing_logger(HeartbeatLogger::init(interval, level, message, logger))
+    }
+
+    /// allows to create a new logger, that can be independently used, no matter what is globally set.
+    ///
+    /// no macros are provided for this case and you probably
+    /// dont want to use this function, but `init()`, if you dont want to build a `CombinedLogger`.
+    ///
+    /// Takes the interval, level and message of the heartbeat as well as the wrapped logger as arguments.
+    /// The background thread is started right away, fails if it could not be spawned.
+    pub fn new(
+        interval: Duration,
+        level: Level,
+        message: &str,
+        logger: Box<dyn SharedLogger>,
+    ) -> io::Result<Box<HeartbeatLogger>> {
+        let inner = Arc::new(Inner {
+            logger,
+            level,
+            message: message.to_owned(),
+            counts: Default::default(),
+        });
+
+        let weak = Arc::downgrade(&inner);
+        let (stop, stopped) = mpsc::channel::<()>();
+        // disconnecting the channel wakes up the thread
+        let thread = BackgroundThread::spawn(
+            "simplelog-heartbeat",
+            move || run(weak, interval, stopped),
+            move || drop(stop),
+        )?;
+
+        Ok(Box::new(HeartbeatLogger { inner, thread }))
+    }
+
+    /// Stops the background thread and waits for it to exit.
+    ///
+    /// No heartbeats are logged afterwards, the HeartbeatLogger keeps forwarding records.
+    pub fn stop(&self) {
+        self.thread.stop();
+    }
+
+    /// Returns the wrapped logger
+    pub fn logger(&self) -> &dyn SharedLogger {
+        &*self.inner.logger
+    }
+}
+
+fn run(inner: Weak<Inner>, interval: Duration, stopped: Receiver<()>) {
+    // nothing is ever sent, the channel is disconnected to stop the thread
+    while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+        let inner = match inner.upgrade() {
+            Some(inner) => inner,
+            None => return,
+        };
+        if inner.level <= log::max_level() {
+            inner.heartbeat();
+        }
+    }
+}
+
+impl Drop for HeartbeatLogger {
+    fn drop(&mut self) {
+        self.thread.stop();
+    }
+}
+
+impl Log for HeartbeatLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.inner.logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.inner.logger.enabled(record.metadata()) {
+            self.inner.counts[record.level() as usize - 1].fetch_add(1, Ordering::Relaxed);
+            self.inner.logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.logger.flush();
+    }
+}
+
+impl SharedLogger for HeartbeatLogger {
+    fn level(&self) -> LevelFilter {
+        self.inner.logger.level()
+    }
+
+    fn config(&self) -> Option<&Config> {
+        self.inner.logger.config()
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn Log> {
+        Box::new(*self)
+    }
+
+    fn name(&self) -> &'static str {
+        "HeartbeatLogger"
+    }
+
+    fn sink_description(&self) -> String {
+        self.inner.logger.sink_description()
+    }
+
+    fn records_written(&self) -> Option<u64> {
+        self.inner.logger.records_written()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Config;
+    use log::{Level, LevelFilter, Record};
+    use std::time::Duration;
+
+    #[test]
+    fn heartbeat() {
+        use crate::{HeartbeatLogger, SharedWriter, WriteLogger};
+        use log::Log;
+
+        log::set_max_level(LevelFilter::Trace);
+        let buffer = SharedWriter::new(Vec::new());
+        let logger = HeartbeatLogger::new(
+            Duration::from_millis(100),
+            Level::Info,
+            "alive",
+            WriteLogger::new(LevelFilter::Info, Config::minimal(), buffer.clone()),
+        )
+        .unwrap();
+        for level in [Level::Warn, Level::Info, Level::Info, Level::Debug] {
+            logger.log(
+                &Record::builder()
+                    .level(level)
+                    .args(format_args!("msg"))
+                    .build(),
+            );
+        }
+        let mut out = String::new();
+        for _ in 0..100 {
+            std::thread::sleep(Duration::from_millis(50));
+            out = String::from_utf8(buffer.lock().clone()).unwrap();
+            if out.contains("alive") {
+                break;
+            }
+        }
+        drop(logger);
+
+        assert!(out.starts_with(
+            "msg\nmsg\nmsg\nalive (since the last heartbeat: 0 errors, 1 warnings, 2 info, 0 debug, 0 trace)\n"
+        ));
+    }
+
+    #[test]
+    fn heartbeat_stop() {
+        use crate::{HeartbeatLogger, SimpleLogger};
+        use std::time::Instant;
+
+        let logger = HeartbeatLogger::new(
+            Duration::from_secs(3600),
+            Level::Info,
+            "alive",
+            SimpleLogger::new(LevelFilter::Info, Config::default()),
+        )
+        .unwrap();
+        let start = Instant::now();
+        logger.stop();
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(logger.thread.is_stopped());
+    }
+}
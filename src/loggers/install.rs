@@ -0,0 +1,139 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the functions installing the loggers as the global logger
+
+use super::earlylog::take_over;
+use super::logging::start_time;
+use crate::{CombinedLogger, InitError, SharedLogger};
+use log::{set_logger, set_max_level, Level, Record, SetLoggerError};
+
+/// Sets the given logger as the global logger.
+///
+/// If an `EarlyLogger` is currently buffering, the logger takes over from it instead.
+pub(crate) fn set_global_logger<L: SharedLogger + 'static>(
+    logger: Box<L>,
+) -> Result<(), SetLoggerError> {
+    try_set_global_logger(logger).map_err(|(err, _)| err)
+}
+
+/// Turns the failure of `init` because of an already initialized Logger into `Ok(false)`,
+/// for the `try_init` functions of the loggers.
+pub(crate) fn tolerate_existing_logger<E: Into<InitError>>(
+    result: Result<(), E>,
+) -> Result<bool, InitError> {
+    match result.map_err(Into::into) {
+        Ok(()) => Ok(true),
+        Err(InitError::SetLogger(_)) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Like `set_global_logger`, but hands the logger back on failure.
+pub(crate) fn try_set_global_logger<L: SharedLogger + 'static>(
+    logger: Box<L>,
+) -> Result<(), (SetLoggerError, Box<L>)> {
+    let level = logger.level();
+    let banner = banner(&*logger);
+    if let Err(logger) = take_over(logger) {
+        let logger: *mut L = Box::into_raw(logger);
+        // SAFETY: the pointer comes from `Box::into_raw` and is only freed again below,
+        // if the logger was not installed.
+        if let Err(err) = set_logger(unsafe { &*logger }) {
+            // SAFETY: the logger was not installed, so the reference
+            // handed to `set_logger` is gone and nothing else points to it.
+            let logger = unsafe { Box::from_raw(logger) };
+            return Err((err, logger));
+        }
+    }
+    set_max_level(level);
+    start_time();
+    if let Some((banner, level)) = banner {
+        log::logger().log(
+            &Record::builder()
+                .args(format_args!("{}", banner))
+                .level(level)
+                .target("simplelog")
+                .build(),
+        );
+    }
+    Ok(())
+}
+
+/// Returns the banner to log after installing the logger and its level, if one is configured
+fn banner(logger: &dyn SharedLogger) -> Option<(String, Level)> {
+    let (app, level, loggers) = match logger.as_any().downcast_ref::<CombinedLogger>() {
+        Some(combined) => {
+            let (app, level) = combined
+                .loggers()
+                .iter()
+                .find_map(|logger| Some((logger.config()?.banner.as_deref()?, logger.level())))?;
+            let loggers = combined
+                .loggers()
+                .iter()
+                .map(|logger| describe(&**logger))
+                .collect::<Vec<_>>()
+                .join("; ");
+            (app, level, loggers)
+        }
+        None => (
+            logger.config()?.banner.as_deref()?,
+            logger.level(),
+            describe(logger),
+        ),
+    };
+
+    let banner = format!(
+        "{} started (simplelog {}, level {}), loggers: {}",
+        app,
+        env!("CARGO_PKG_VERSION"),
+        logger.level(),
+        loggers
+    );
+    Some((banner, level.to_level()?.min(Level::Info)))
+}
+
+/// Describes a logger for the banner, e.g. `WriteLogger (INFO) to app.log`
+fn describe(logger: &dyn SharedLogger) -> String {
+    format!(
+        "{} ({}) to {}",
+        logger.name(),
+        logger.level(),
+        logger.sink_description()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::banner;
+    use crate::{CombinedLogger, Config, ConfigBuilder, SimpleLogger, WriteLogger};
+    use log::{Level, LevelFilter};
+
+    #[test]
+    fn banner_lists_sinks() {
+        let combined = CombinedLogger::new(vec![
+            SimpleLogger::new(LevelFilter::Warn, Config::default()),
+            WriteLogger::new(
+                LevelFilter::Debug,
+                ConfigBuilder::new().set_banner("my_app", "1.0.0").build(),
+                Vec::new(),
+            )
+            .with_sink_description("debug.log"),
+        ]);
+        let (banner, level) = banner(&*combined).unwrap();
+        assert_eq!(
+            banner,
+            format!(
+                "my_app 1.0.0 started (simplelog {}, level DEBUG), loggers: \
+                 SimpleLogger (WARN) to stdout, errors to stderr; \
+                 WriteLogger (DEBUG) to debug.log",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+        assert_eq!(level, Level::Info);
+    }
+}
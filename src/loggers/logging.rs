@@ -1,25 +1,19 @@
-use crate::config::{TargetPadding, TimeFormat};
-use crate::{Config, LevelPadding, ThreadLogMode, ThreadPadding};
-use log::{LevelFilter, Record};
+use crate::config::{
+    TargetAbbreviation, TargetPadding, TargetTruncation, TimeFormat, UnixPrecision,
+};
+#[cfg(feature = "termcolor")]
+use crate::Style;
+use crate::{Config, HeaderAlignment, LevelPadding, MultiLineMode, ThreadLogMode, ThreadPadding};
+use log::{LevelFilter, Metadata, Record};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt::{self, Display};
 use std::io::{Error, Write};
+use std::sync::OnceLock;
 use std::thread;
-#[cfg(all(feature = "termcolor", feature = "ansi_term"))]
-use termcolor::Color;
-
-#[cfg(all(feature = "termcolor", feature = "ansi_term"))]
-pub fn termcolor_to_ansiterm(color: &Color) -> Option<ansi_term::Color> {
-    match color {
-        Color::Black => Some(ansi_term::Color::Black),
-        Color::Red => Some(ansi_term::Color::Red),
-        Color::Green => Some(ansi_term::Color::Green),
-        Color::Yellow => Some(ansi_term::Color::Yellow),
-        Color::Blue => Some(ansi_term::Color::Blue),
-        Color::Magenta => Some(ansi_term::Color::Purple),
-        Color::Cyan => Some(ansi_term::Color::Cyan),
-        Color::White => Some(ansi_term::Color::White),
-        _ => None,
-    }
-}
+use std::time::{Duration, Instant, SystemTime};
+#[cfg(feature = "termcolor")]
+use termcolor::{Ansi, ColorSpec, WriteColor};
 
 #[inline(always)]
 pub fn try_log<W>(config: &Config, record: &Record<'_>, write: &mut W) -> Result<(), Error>
@@ -29,11 +23,64 @@ where
     if should_skip(config, record) {
         return Ok(());
     }
+    config.count_record();
+
+    if config.multi_line_mode == MultiLineMode::Off {
+        write_header(config, record, write)?;
+        return write_args(record, write, config, None);
+    }
+
+    let mut recorder = HeaderRecorder::new(write);
+    write_header(config, record, &mut recorder)?;
+    let header = recorder.finish();
+    write_args(
+        record,
+        write,
+        config,
+        Some(&mut |write: &mut W| header.write_continuation(write, config.multi_line_mode)),
+    )
+}
+
+#[inline(always)]
+pub fn write_header<W>(config: &Config, record: &Record<'_>, write: &mut W) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    if config.header_alignment == HeaderAlignment::Off {
+        return write_header_parts(config, record, write);
+    }
+
+    let mut counter = WidthCounter::new(write);
+    write_header_parts(config, record, &mut counter)?;
+    let padding = config.header_padding(counter.width());
+    write!(write, "{:1$}", "", padding)
+}
+
+#[inline(always)]
+fn write_header_parts<W>(config: &Config, record: &Record<'_>, write: &mut W) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    if let Some(prefix) = &config.prefix {
+        write_prefix(write, prefix)?;
+    }
+
+    if config.sequence <= record.level() && config.sequence != LevelFilter::Off {
+        write_sequence(write, config)?;
+    }
 
     if config.time <= record.level() && config.time != LevelFilter::Off {
         write_time(write, config)?;
     }
 
+    if config.hostname <= record.level() && config.hostname != LevelFilter::Off {
+        write_hostname(write, config)?;
+    }
+
+    if config.pid <= record.level() && config.pid != LevelFilter::Off {
+        write_pid(write)?;
+    }
+
     if config.level <= record.level() && config.level != LevelFilter::Off {
         write_level(record, write, config)?;
     }
@@ -54,81 +101,256 @@ where
     }
 
     if config.location <= record.level() && config.location != LevelFilter::Off {
-        write_location(record, write)?;
+        write_location(record, write, config)?;
     }
 
-    if config.module <= record.level() && config.module != LevelFilter::Off {
+    if config.module <= record.level()
+        && config.module != LevelFilter::Off
+        && !(config.module_only_on_target_override && record.module_path() == Some(record.target()))
+    {
         write_module(record, write)?;
     }
 
-    #[cfg(feature = "paris")]
-    return write_args(
-        record,
-        write,
-        config.enable_paris_formatting,
-        &config.line_ending,
-    );
-    #[cfg(not(feature = "paris"))]
-    return write_args(record, write, &config.line_ending);
+    Ok(())
+}
+
+/// Capacity kept by the render buffer of a thread after logging a very long record
+const MAX_BUFFER_CAPACITY: usize = 64 * 1024;
+
+thread_local! {
+    /// Buffer records are rendered into before being written in one go, reused to avoid allocations
+    static BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Calls `f` with an empty buffer to render a record into
+pub(crate) fn with_render_buffer<F>(f: F)
+where
+    F: FnOnce(&mut Vec<u8>),
+{
+    let mut f = Some(f);
+    let rendered = BUFFER.try_with(|buffer| match buffer.try_borrow_mut() {
+        Ok(mut buffer) => {
+            (f.take().unwrap())(&mut buffer);
+            buffer.clear();
+            buffer.shrink_to(MAX_BUFFER_CAPACITY);
+        }
+        // a record is logged while rendering another one on this thread
+        Err(_) => (f.take().unwrap())(&mut Vec::new()),
+    });
+    if rendered.is_err() {
+        (f.take().unwrap())(&mut Vec::new());
+    }
+}
+
+static START_TIME: OnceLock<Instant> = OnceLock::new();
+
+/// Returns the time logging was initialized, which is the reference point of `TimeFormat::Uptime`
+pub(crate) fn start_time() -> Instant {
+    *START_TIME.get_or_init(Instant::now)
+}
+
+/// Displays the time elapsed since the previous record in a human readable unit
+pub(crate) struct Delta(pub Duration);
+
+impl Display for Delta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let micros = self.0.as_secs_f64() * 1_000_000.0;
+        if micros < 1_000.0 {
+            write!(f, "+{:.1}µs", micros)
+        } else if micros < 1_000_000.0 {
+            write!(f, "+{:.1}ms", micros / 1_000.0)
+        } else {
+            write!(f, "+{:.3}s", micros / 1_000_000.0)
+        }
+    }
+}
+
+/// Returns the time since the unix epoch in the given precision
+pub(crate) fn unix_timestamp(time: time::OffsetDateTime, precision: UnixPrecision) -> i128 {
+    match precision {
+        UnixPrecision::Seconds => time.unix_timestamp() as i128,
+        UnixPrecision::Millis => time.unix_timestamp_nanos() / 1_000_000,
+        UnixPrecision::Micros => time.unix_timestamp_nanos() / 1_000,
+    }
+}
+
+/// Formats the current time using chrono
+#[cfg(feature = "chrono")]
+pub(crate) fn chrono_time<'a>(
+    format: &'a str,
+    local: bool,
+    config: &Config,
+    now: SystemTime,
+) -> impl Display + 'a {
+    use chrono::{DateTime, FixedOffset, Local, Utc};
+
+    let time = if local {
+        DateTime::<Local>::from(now).fixed_offset()
+    } else {
+        // UtcOffset is limited to less than 24 hours, just like FixedOffset
+        let offset = FixedOffset::east_opt(config.time_offset().whole_seconds()).unwrap();
+        DateTime::<Utc>::from(now).with_timezone(&offset)
+    };
+    time.format(format)
 }
 
 #[inline(always)]
 pub fn write_time<W>(write: &mut W, config: &Config) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    let now = config.now();
+    match &config.time_cache {
+        Some(cache) if config.time_format.is_calendar() => {
+            cache.write(write, now, |buffer| format_time(buffer, config, now))?
+        }
+        _ => format_time(write, config, now)?,
+    }
+    write!(write, " ")
+}
+
+fn format_time<W>(write: &mut W, config: &Config, now: SystemTime) -> Result<(), Error>
 where
     W: Write + Sized,
 {
     use time::error::Format;
     use time::format_description::well_known::*;
 
-    let time = time::OffsetDateTime::now_utc().to_offset(config.time_offset);
+    let time = time::OffsetDateTime::from(now).to_offset(config.time_offset());
     let res = match config.time_format {
+        TimeFormat::Uptime(precision) => {
+            let uptime = start_time().elapsed().as_secs_f64();
+            // five integer digits, as dmesg does
+            let width = if precision > 0 { 6 + precision } else { 5 };
+            return write!(write, "[{:>1$.2$}]", uptime, width, precision);
+        }
+        TimeFormat::Unix(precision) => return write!(write, "{}", unix_timestamp(time, precision)),
+        TimeFormat::Delta(ref clock) => return write!(write, "{}", Delta(clock.tick())),
         TimeFormat::Rfc2822 => time.format_into(write, &Rfc2822),
         TimeFormat::Rfc3339 => time.format_into(write, &Rfc3339),
         TimeFormat::Custom(format) => time.format_into(write, &format),
+        TimeFormat::Owned(ref format) => time.format_into(write, format),
+        #[cfg(feature = "chrono")]
+        TimeFormat::Chrono {
+            ref format,
+            local,
+            valid,
+        } => {
+            if !valid {
+                return Err(Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "invalid chrono time format",
+                ));
+            }
+            return write!(write, "{}", chrono_time(format, local, config, now));
+        }
     };
     match res {
-        Err(Format::StdIo(err)) => return Err(err),
+        Err(Format::StdIo(err)) => Err(err),
         Err(err) => panic!("Invalid time format: {}", err),
-        _ => {}
-    };
+        Ok(_) => Ok(()),
+    }
+}
 
-    write!(write, " ")?;
-    Ok(())
+#[inline(always)]
+pub fn write_prefix<W>(write: &mut W, prefix: &str) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    write!(write, "[{}] ", prefix)
 }
 
 #[inline(always)]
-pub fn write_level<W>(record: &Record<'_>, write: &mut W, config: &Config) -> Result<(), Error>
+pub fn write_sequence<W>(write: &mut W, config: &Config) -> Result<(), Error>
 where
     W: Write + Sized,
 {
-    #[cfg(all(feature = "termcolor", feature = "ansi_term"))]
-    let color = match &config.level_color[record.level() as usize] {
-        Some(termcolor) => {
-            if config.write_log_enable_colors {
-                termcolor_to_ansiterm(termcolor)
-            } else {
-                None
-            }
-        }
-        None => None,
-    };
+    write!(write, "#{:06} ", config.next_sequence())
+}
 
-    let level = match config.level_padding {
-        LevelPadding::Left => format!("[{: >5}]", record.level()),
-        LevelPadding::Right => format!("[{: <5}]", record.level()),
-        LevelPadding::Off => format!("[{}]", record.level()),
-    };
+/// Returns the configured hostname or the one of the machine, which is determined once
+pub(crate) fn hostname(config: &Config) -> &str {
+    if let Some(hostname) = &config.hostname_override {
+        return hostname;
+    }
 
-    #[cfg(all(feature = "termcolor", feature = "ansi_term"))]
-    match color {
-        Some(c) => write!(write, "{} ", c.paint(level))?,
-        None => write!(write, "{} ", level)?,
-    };
+    #[cfg(feature = "hostname")]
+    {
+        static HOSTNAME: OnceLock<String> = OnceLock::new();
+        HOSTNAME.get_or_init(|| gethostname::gethostname().to_string_lossy().into_owned())
+    }
+    #[cfg(not(feature = "hostname"))]
+    "<unknown>"
+}
 
-    #[cfg(not(feature = "ansi_term"))]
-    write!(write, "{} ", level)?;
+#[inline(always)]
+pub fn write_hostname<W>(write: &mut W, config: &Config) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    write!(write, "{} ", hostname(config))
+}
 
-    Ok(())
+/// Returns the id of the current process, which is determined once
+pub(crate) fn pid() -> u32 {
+    static PID: OnceLock<u32> = OnceLock::new();
+    *PID.get_or_init(std::process::id)
+}
+
+#[inline(always)]
+pub fn write_pid<W>(write: &mut W) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    write!(write, "[{}] ", pid())
+}
+
+#[inline(always)]
+pub fn write_level<W>(record: &Record<'_>, write: &mut W, config: &Config) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    #[cfg(feature = "termcolor")]
+    if config.write_log_enable_colors {
+        let style = config.level_style[record.level() as usize];
+        if style != Style::new() {
+            let mut ansi = Ansi::new(&mut *write);
+            ansi.set_color(&style.to_color_spec())?;
+            write_level_label(record, &mut ansi, config)?;
+            ansi.reset()?;
+            return write!(write, " ");
+        }
+    }
+
+    write_level_label(record, write, config)?;
+    write!(write, " ")
+}
+
+#[inline(always)]
+fn write_level_label<W>(record: &Record<'_>, write: &mut W, config: &Config) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    let level = record.level() as usize;
+    let icon = config.level_icons[level];
+    match icon {
+        Some(icon) if config.level_icon_only => write!(write, "{}", icon),
+        _ => {
+            if let Some(icon) = icon {
+                write!(write, "{} ", icon)?;
+            }
+            let label = config.level_labels[level];
+            match config.level_padding {
+                LevelPadding::Left => {
+                    write!(write, "[{: >1$}]", label, config.level_label_width())
+                }
+                LevelPadding::Right => {
+                    write!(write, "[{: <1$}]", label, config.level_label_width())
+                }
+                LevelPadding::Off => write!(write, "[{}]", label),
+            }
+        }
+    }
 }
 
 #[inline(always)]
@@ -136,26 +358,25 @@ pub fn write_target<W>(record: &Record<'_>, write: &mut W, config: &Config) -> R
 where
     W: Write + Sized,
 {
-    // dbg!(&config.target_padding);
+    let target = abbreviate_target(
+        target_alias(config, record.target()),
+        config.target_abbreviation,
+    );
+    let target = match config.target_padding {
+        TargetPadding::Left(pad) | TargetPadding::Right(pad) => {
+            truncate_target(target, pad, config.target_truncation)
+        }
+        TargetPadding::Off => target,
+    };
     match config.target_padding {
         TargetPadding::Left(pad) => {
-            write!(
-                write,
-                "{target:>pad$}: ",
-                pad = pad,
-                target = record.target()
-            )?;
+            write!(write, "{target:>pad$}: ", pad = pad, target = target)?;
         }
         TargetPadding::Right(pad) => {
-            write!(
-                write,
-                "{target:<pad$}: ",
-                pad = pad,
-                target = record.target()
-            )?;
+            write!(write, "{target:<pad$}: ", pad = pad, target = target)?;
         }
         TargetPadding::Off => {
-            write!(write, "{}: ", record.target())?;
+            write!(write, "{}: ", target)?;
         }
     }
 
@@ -163,19 +384,96 @@ where
 }
 
 #[inline(always)]
-pub fn write_location<W>(record: &Record<'_>, write: &mut W) -> Result<(), Error>
+pub fn truncate_target(
+    target: Cow<'_, str>,
+    width: usize,
+    truncation: TargetTruncation,
+) -> Cow<'_, str> {
+    let len = target.chars().count();
+    let (ellipsis, at_start) = match truncation {
+        _ if len <= width => return target,
+        TargetTruncation::Off => return target,
+        TargetTruncation::Start(ellipsis) => (ellipsis, true),
+        TargetTruncation::End(ellipsis) => (ellipsis, false),
+    };
+
+    // drop the ellipsis, if it doesn't leave any room for the target itself
+    let ellipsis = match width.checked_sub(ellipsis.chars().count()) {
+        Some(keep) if keep > 0 => ellipsis,
+        _ => "",
+    };
+    let keep = width - ellipsis.chars().count();
+    Cow::Owned(if at_start {
+        let kept: String = target.chars().skip(len - keep).collect();
+        format!("{}{}", ellipsis, kept)
+    } else {
+        let kept: String = target.chars().take(keep).collect();
+        format!("{}{}", kept, ellipsis)
+    })
+}
+
+/// Returns the alias of the longest matching entry added with `add_target_alias` or the target itself
+#[inline(always)]
+pub fn target_alias<'a>(config: &'a Config, target: &'a str) -> &'a str {
+    config
+        .target_aliases
+        .iter()
+        .filter(|(filter, _)| filter_matches(filter, target))
+        .max_by_key(|(filter, _)| filter.len())
+        .map_or(target, |(_, alias)| alias)
+}
+
+#[inline(always)]
+pub fn abbreviate_target(target: &str, abbreviation: TargetAbbreviation) -> Cow<'_, str> {
+    match abbreviation {
+        TargetAbbreviation::Off => Cow::Borrowed(target),
+        TargetAbbreviation::LastSegments(n) => {
+            match target.rmatch_indices("::").nth(n.max(1) - 1) {
+                Some((idx, _)) => Cow::Borrowed(&target[idx + 2..]),
+                None => Cow::Borrowed(target),
+            }
+        }
+        TargetAbbreviation::FirstLetters => match target.rfind("::") {
+            Some(idx) => {
+                let mut abbreviated = String::with_capacity(target.len());
+                for segment in target[..idx].split("::") {
+                    abbreviated.extend(segment.chars().next());
+                    abbreviated.push_str("::");
+                }
+                abbreviated.push_str(&target[idx + 2..]);
+                Cow::Owned(abbreviated)
+            }
+            None => Cow::Borrowed(target),
+        },
+    }
+}
+
+#[inline(always)]
+pub fn write_location<W>(record: &Record<'_>, write: &mut W, config: &Config) -> Result<(), Error>
 where
     W: Write + Sized,
 {
-    let file = record.file().unwrap_or("<unknown>");
-    if let Some(line) = record.line() {
-        write!(write, "[{}:{}] ", file, line)?;
+    let file = location_file(record, config);
+    if !config.location_line {
+        write!(write, "[{}] ", file)?;
+    } else if let Some(line) = record.line() {
+        write!(write, "[{}{}{}] ", file, config.location_separator, line)?;
     } else {
-        write!(write, "[{}:<unknown>] ", file)?;
+        write!(write, "[{}{}<unknown>] ", file, config.location_separator)?;
     }
     Ok(())
 }
 
+#[inline(always)]
+pub fn location_file<'a>(record: &Record<'a>, config: &Config) -> &'a str {
+    match record.file() {
+        // split on both separators, the source may have been compiled on another platform
+        Some(file) if config.location_basename => file.rsplit(['/', '\\']).next().unwrap_or(file),
+        Some(file) => file,
+        None => "<unknown>",
+    }
+}
+
 #[inline(always)]
 pub fn write_module<W>(record: &Record<'_>, write: &mut W) -> Result<(), Error>
 where
@@ -186,90 +484,564 @@ where
     Ok(())
 }
 
-pub fn write_thread_name<W>(write: &mut W, config: &Config) -> Result<(), Error>
+/// Rendered id and name of a thread
+struct ThreadInfo {
+    id: String,
+    name: Option<String>,
+}
+
+impl ThreadInfo {
+    fn new(thread: &thread::Thread) -> ThreadInfo {
+        let id = format!("{:?}", thread.id());
+        let id = id.replace("ThreadId(", "");
+        let id = id.replace(')', "");
+        ThreadInfo {
+            id,
+            name: thread.name().map(String::from),
+        }
+    }
+}
+
+thread_local! {
+    // neither id nor name of a thread change, so render them only once
+    static THREAD_INFO: ThreadInfo = ThreadInfo::new(&thread::current());
+    /// Time and thread of the `LogRecordOwned` currently rendered on this thread
+    static SNAPSHOT: RefCell<Option<(SystemTime, ThreadInfo)>> = const { RefCell::new(None) };
+}
+
+/// Calls `f`, while the given time and thread are rendered instead of the current ones
+pub(crate) fn with_snapshot<F, R>(time: SystemTime, id: &str, name: Option<&str>, f: F) -> R
 where
-    W: Write + Sized,
+    F: FnOnce() -> R,
 {
-    if let Some(name) = thread::current().name() {
-        match config.thread_padding {
-            ThreadPadding::Left { 0: qty } => {
-                write!(write, "({name:>0$}) ", qty, name = name)?;
-            }
-            ThreadPadding::Right { 0: qty } => {
-                write!(write, "({name:<0$}) ", qty, name = name)?;
-            }
-            ThreadPadding::Off => {
-                write!(write, "({}) ", name)?;
-            }
+    let info = ThreadInfo {
+        id: id.to_string(),
+        name: name.map(String::from),
+    };
+    let previous = SNAPSHOT.with(|snapshot| snapshot.replace(Some((time, info))));
+    let res = f();
+    SNAPSHOT.with(|snapshot| snapshot.replace(previous));
+    res
+}
+
+/// Returns the time of the `LogRecordOwned` currently rendered on this thread, if any
+pub(crate) fn snapshot_time() -> Option<SystemTime> {
+    SNAPSHOT
+        .try_with(|snapshot| snapshot.borrow().as_ref().map(|(time, _)| *time))
+        .ok()
+        .flatten()
+}
+
+/// Calls `f` with the id and name of the current thread
+/// (or the thread of the `LogRecordOwned` currently rendered)
+pub(crate) fn with_thread_info<F, R>(f: F) -> R
+where
+    F: FnOnce(&str, Option<&str>) -> R,
+{
+    let mut f = Some(f);
+    let snapshot = SNAPSHOT.try_with(|snapshot| {
+        let snapshot = snapshot.borrow();
+        let (_, info) = snapshot.as_ref()?;
+        Some((f.take().unwrap())(&info.id, info.name.as_deref()))
+    });
+    if let Ok(Some(res)) = snapshot {
+        return res;
+    }
+
+    let res = THREAD_INFO.try_with(|info| (f.take().unwrap())(&info.id, info.name.as_deref()));
+    match res {
+        Ok(res) => res,
+        // the thread local was already destroyed, e.g. when logging from another destructor
+        Err(_) => {
+            let info = ThreadInfo::new(&thread::current());
+            (f.take().unwrap())(&info.id, info.name.as_deref())
         }
-    } else if config.thread_log_mode == ThreadLogMode::Both {
-        write_thread_id(write, config)?;
     }
+}
 
-    Ok(())
+pub fn write_thread_name<W>(write: &mut W, config: &Config) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    with_thread_info(|id, name| match name {
+        Some(name) => write_thread_part(write, name, config),
+        None if config.thread_log_mode == ThreadLogMode::Both => {
+            write_thread_part(write, id, config)
+        }
+        None => Ok(()),
+    })
 }
 
 pub fn write_thread_id<W>(write: &mut W, config: &Config) -> Result<(), Error>
 where
     W: Write + Sized,
 {
-    let id = format!("{:?}", thread::current().id());
-    let id = id.replace("ThreadId(", "");
-    let id = id.replace(")", "");
+    with_thread_info(|id, _| write_thread_part(write, id, config))
+}
+
+#[inline(always)]
+fn write_thread_part<W>(write: &mut W, part: &str, config: &Config) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
     match config.thread_padding {
         ThreadPadding::Left { 0: qty } => {
-            write!(write, "({id:>0$}) ", qty, id = id)?;
+            write!(write, "({part:>0$}) ", qty, part = part)?;
         }
         ThreadPadding::Right { 0: qty } => {
-            write!(write, "({id:<0$}) ", qty, id = id)?;
+            write!(write, "({part:<0$}) ", qty, part = part)?;
         }
         ThreadPadding::Off => {
-            write!(write, "({}) ", id)?;
+            write!(write, "({}) ", part)?;
         }
     }
     Ok(())
 }
 
+/// Callback writing the start of a continuation line
+pub type Continuation<'a, W> = &'a mut dyn FnMut(&mut W) -> Result<(), Error>;
+
+/// Writes the message followed by the line ending.
+///
+/// If given, `continuation` is called after every line break inside the message.
 #[inline(always)]
-#[cfg(feature = "paris")]
 pub fn write_args<W>(
     record: &Record<'_>,
     write: &mut W,
-    with_colors: bool,
-    line_ending: &str,
+    config: &Config,
+    continuation: Option<Continuation<'_, W>>,
 ) -> Result<(), Error>
 where
     W: Write + Sized,
 {
-    write!(
-        write,
-        "{}{}",
-        crate::__private::paris::formatter::format_string(
-            format!("{}", record.args()),
-            with_colors
-        ),
-        line_ending
-    )?;
+    match continuation {
+        Some(continuation) => write_record_message(
+            record,
+            &mut ContinuationWriter {
+                inner: write,
+                continuation,
+            },
+            config,
+        )?,
+        None => write_record_message(record, write, config)?,
+    }
+    write!(write, "{}", config.line_ending)?;
     Ok(())
 }
 
+/// Like `write_args`, but prints the parts of the message matching a highlight of the config in its style.
+///
+/// `line_style` is restored after every highlight, if the whole line is colorized.
+#[cfg(feature = "termcolor")]
+pub fn write_args_highlighted<W>(
+    record: &Record<'_>,
+    write: &mut W,
+    config: &Config,
+    mut continuation: Option<Continuation<'_, W>>,
+    line_style: Option<&Style>,
+) -> Result<(), Error>
+where
+    W: WriteColor + Sized,
+{
+    if config.highlights.is_empty() {
+        return write_args(record, write, config, continuation);
+    }
+
+    let mut message = Vec::new();
+    write_record_message(record, &mut message, config)?;
+    let message = String::from_utf8_lossy(&message);
+
+    let mut write_text = |write: &mut W, text: &str| match &mut continuation {
+        Some(continuation) => ContinuationWriter {
+            inner: write,
+            continuation: &mut **continuation,
+        }
+        .write_all(text.as_bytes()),
+        None => write.write_all(text.as_bytes()),
+    };
+
+    let mut position = 0;
+    while let Some((range, style)) = config
+        .highlights
+        .iter()
+        .filter_map(|(highlight, style)| Some((highlight.find_at(&message, position)?, style)))
+        .min_by_key(|(range, _)| range.start)
+    {
+        write_text(write, &message[position..range.start])?;
+        write.set_color(&style.to_color_spec())?;
+        write_text(write, &message[range.clone()])?;
+        write.reset()?;
+        if let Some(line_style) = line_style {
+            write.set_color(&line_style.to_color_spec())?;
+        }
+        position = range.end;
+    }
+    write_text(write, &message[position..])?;
+    write!(write, "{}", config.line_ending)
+}
+
 #[inline(always)]
-#[cfg(not(feature = "paris"))]
-pub fn write_args<W>(record: &Record<'_>, write: &mut W, line_ending: &str) -> Result<(), Error>
+fn write_record_message<W>(record: &Record<'_>, write: &mut W, config: &Config) -> Result<(), Error>
 where
     W: Write + Sized,
 {
-    write!(write, "{}{}", record.args(), line_ending)?;
-    Ok(())
+    #[cfg(feature = "paris")]
+    let args = crate::__private::paris::formatter::format_string(
+        format!("{}", record.args()),
+        config.enable_paris_formatting,
+    );
+    #[cfg(not(feature = "paris"))]
+    let args = record.args();
+
+    let indentation = crate::scope::indentation();
+    if indentation > 0 {
+        write!(write, "{:1$}", "", indentation)?;
+    }
+    write_message(write, &args, config)
+}
+
+#[inline(always)]
+fn write_message<W, D>(write: &mut W, args: &D, config: &Config) -> Result<(), Error>
+where
+    W: Write + Sized,
+    D: Display + ?Sized,
+{
+    if config.escape_control_chars {
+        let mut escaper = ControlEscaper { inner: write };
+        if config.strip_ansi {
+            write!(AnsiStripper::new(&mut escaper), "{}", args)
+        } else {
+            write!(escaper, "{}", args)
+        }
+    } else if config.strip_ansi {
+        write!(AnsiStripper::new(write), "{}", args)
+    } else {
+        write!(write, "{}", args)
+    }
+}
+
+/// Writer escaping control characters (except tabs) and unicode line breaks
+struct ControlEscaper<'a, W: Write> {
+    inner: &'a mut W,
+}
+
+impl<W: Write> Write for ControlEscaper<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        // `write!` hands over complete `str` fragments, anything else is escaped bytewise
+        let text = String::from_utf8_lossy(buf);
+        let mut start = 0;
+        for (i, c) in text.char_indices() {
+            if c == '\t' || !(c.is_control() || c == '\u{2028}' || c == '\u{2029}') {
+                continue;
+            }
+            self.inner.write_all(text[start..i].as_bytes())?;
+            match c {
+                '\n' => self.inner.write_all(b"\\n")?,
+                '\r' => self.inner.write_all(b"\\r")?,
+                c if (c as u32) < 0x100 => write!(self.inner, "\\x{:02x}", c as u32)?,
+                c => write!(self.inner, "\\u{{{:04x}}}", c as u32)?,
+            }
+            start = i + c.len_utf8();
+        }
+        self.inner.write_all(text[start..].as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush()
+    }
+}
+
+/// Writer calling a continuation after every line break written through it
+struct ContinuationWriter<'a, 'b, W: Write> {
+    inner: &'a mut W,
+    continuation: Continuation<'b, W>,
+}
+
+impl<W: Write> Write for ContinuationWriter<'_, '_, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        for line in buf.split_inclusive(|&b| b == b'\n') {
+            self.inner.write_all(line)?;
+            if line.ends_with(b"\n") {
+                (self.continuation)(self.inner)?;
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush()
+    }
+}
+
+enum HeaderPart {
+    Bytes(Vec<u8>),
+    #[cfg(feature = "termcolor")]
+    Color(ColorSpec),
+    #[cfg(feature = "termcolor")]
+    Reset,
+}
+
+/// Writer passing everything through, while keeping a copy to be repeated for continuation lines
+pub struct HeaderRecorder<'a, W: Write> {
+    inner: &'a mut W,
+    parts: Vec<HeaderPart>,
+}
+
+impl<'a, W: Write> HeaderRecorder<'a, W> {
+    pub fn new(inner: &'a mut W) -> HeaderRecorder<'a, W> {
+        HeaderRecorder {
+            inner,
+            parts: Vec::new(),
+        }
+    }
+
+    pub fn finish(self) -> Header {
+        Header { parts: self.parts }
+    }
+}
+
+impl<W: Write> Write for HeaderRecorder<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let written = self.inner.write(buf)?;
+        match self.parts.last_mut() {
+            Some(HeaderPart::Bytes(bytes)) => bytes.extend_from_slice(&buf[..written]),
+            _ => self.parts.push(HeaderPart::Bytes(buf[..written].to_vec())),
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "termcolor")]
+impl<W: WriteColor> WriteColor for HeaderRecorder<'_, W> {
+    fn supports_color(&self) -> bool {
+        self.inner.supports_color()
+    }
+
+    fn set_color(&mut self, spec: &ColorSpec) -> Result<(), Error> {
+        self.parts.push(HeaderPart::Color(spec.clone()));
+        self.inner.set_color(spec)
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        self.parts.push(HeaderPart::Reset);
+        self.inner.reset()
+    }
+}
+
+/// The recorded header (time, level, thread, ...) of a record
+pub struct Header {
+    parts: Vec<HeaderPart>,
+}
+
+// without termcolor, headers consist of bytes only
+#[cfg_attr(not(feature = "termcolor"), allow(irrefutable_let_patterns))]
+impl Header {
+    /// Width of the header in characters, ignoring any ANSI escape sequences
+    fn width(&self) -> usize {
+        let mut text = Vec::new();
+        for part in &self.parts {
+            if let HeaderPart::Bytes(bytes) = part {
+                let _ = AnsiStripper::new(&mut text).write_all(bytes);
+            }
+        }
+        String::from_utf8_lossy(&text).chars().count()
+    }
+
+    /// Writes the start of a continuation line
+    pub fn write_continuation<W>(&self, write: &mut W, mode: MultiLineMode) -> Result<(), Error>
+    where
+        W: Write + Sized,
+    {
+        match mode {
+            MultiLineMode::Off => Ok(()),
+            MultiLineMode::Indent => write!(write, "{:1$}", "", self.width()),
+            MultiLineMode::RepeatHeader => {
+                for part in &self.parts {
+                    if let HeaderPart::Bytes(bytes) = part {
+                        write.write_all(bytes)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes the start of a continuation line including the colors of the header
+    #[cfg(feature = "termcolor")]
+    pub fn write_continuation_color<W>(
+        &self,
+        write: &mut W,
+        mode: MultiLineMode,
+    ) -> Result<(), Error>
+    where
+        W: WriteColor + Sized,
+    {
+        if mode != MultiLineMode::RepeatHeader {
+            return self.write_continuation(write, mode);
+        }
+
+        for part in &self.parts {
+            match part {
+                HeaderPart::Bytes(bytes) => write.write_all(bytes)?,
+                HeaderPart::Color(spec) => write.set_color(spec)?,
+                HeaderPart::Reset => write.reset()?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AnsiState {
+    Text,
+    /// After an ESC
+    Escape,
+    /// Inside a CSI sequence (`ESC [`), terminated by a byte in 0x40..=0x7E
+    Csi,
+    /// Inside an OSC sequence (`ESC ]`), terminated by BEL or `ESC \`
+    Osc,
+    /// After an ESC inside an OSC sequence
+    OscEscape,
+}
+
+/// Writer removing ANSI escape sequences from everything written through it
+pub struct AnsiStripper<'a, W: Write> {
+    inner: &'a mut W,
+    state: AnsiState,
+}
+
+impl<'a, W: Write> AnsiStripper<'a, W> {
+    pub fn new(inner: &'a mut W) -> AnsiStripper<'a, W> {
+        AnsiStripper {
+            inner,
+            state: AnsiState::Text,
+        }
+    }
+}
+
+impl<W: Write> Write for AnsiStripper<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let mut start = 0;
+        for (i, &byte) in buf.iter().enumerate() {
+            self.state = match (self.state, byte) {
+                (AnsiState::Text, 0x1b) => {
+                    self.inner.write_all(&buf[start..i])?;
+                    AnsiState::Escape
+                }
+                (AnsiState::Text, _) => continue,
+                (AnsiState::Escape, b'[') => AnsiState::Csi,
+                (AnsiState::Escape, b']') => AnsiState::Osc,
+                (AnsiState::Csi, 0x40..=0x7e) | (AnsiState::Escape, _) => {
+                    start = i + 1;
+                    AnsiState::Text
+                }
+                (AnsiState::Csi, _) => AnsiState::Csi,
+                (AnsiState::Osc, 0x07) | (AnsiState::OscEscape, b'\\') => {
+                    start = i + 1;
+                    AnsiState::Text
+                }
+                (AnsiState::Osc, 0x1b) => AnsiState::OscEscape,
+                (AnsiState::Osc, _) | (AnsiState::OscEscape, _) => AnsiState::Osc,
+            };
+        }
+        if self.state == AnsiState::Text {
+            self.inner.write_all(&buf[start..])?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush()
+    }
+}
+
+/// Writer passing everything through, while counting the characters written, ignoring any ANSI escape sequences
+pub struct WidthCounter<'a, W: Write> {
+    inner: &'a mut W,
+    chars: CharCount,
+    state: AnsiState,
+}
+
+impl<'a, W: Write> WidthCounter<'a, W> {
+    pub fn new(inner: &'a mut W) -> WidthCounter<'a, W> {
+        WidthCounter {
+            inner,
+            chars: CharCount(0),
+            state: AnsiState::Text,
+        }
+    }
+
+    /// Number of characters written so far
+    pub fn width(&self) -> usize {
+        self.chars.0
+    }
+}
+
+impl<W: Write> Write for WidthCounter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let written = self.inner.write(buf)?;
+        let mut stripper = AnsiStripper {
+            inner: &mut self.chars,
+            state: self.state,
+        };
+        stripper.write_all(&buf[..written])?;
+        self.state = stripper.state;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "termcolor")]
+impl<W: WriteColor> WriteColor for WidthCounter<'_, W> {
+    fn supports_color(&self) -> bool {
+        self.inner.supports_color()
+    }
+
+    fn set_color(&mut self, spec: &ColorSpec) -> Result<(), Error> {
+        self.inner.set_color(spec)
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        self.inner.reset()
+    }
+}
+
+/// Sink counting the UTF-8 characters written into it
+struct CharCount(usize);
+
+impl Write for CharCount {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        self.0 += buf.iter().filter(|&&byte| byte & 0xc0 != 0x80).count();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 #[inline(always)]
 pub fn should_skip(config: &Config, record: &Record<'_>) -> bool {
+    should_skip_metadata(config, record.metadata())
+}
+
+/// Like `should_skip`, but only needs the metadata of a record, e.g. for `Log::enabled`
+#[inline(always)]
+#[allow(clippy::collapsible_match)]
+pub fn should_skip_metadata(config: &Config, metadata: &Metadata<'_>) -> bool {
     // If a module path and allowed list are available
-    match (record.target(), &*config.filter_allow) {
+    match (metadata.target(), &*config.filter_allow) {
         (path, allowed) if !allowed.is_empty() => {
             // Check that the module path matches at least one allow filter
-            if !allowed.iter().any(|v| path.starts_with(&**v)) {
+            if !allowed.iter().any(|v| filter_matches(v, path)) {
                 // If not, skip any further writing
                 return true;
             }
@@ -278,10 +1050,10 @@ pub fn should_skip(config: &Config, record: &Record<'_>) -> bool {
     }
 
     // If a module path and ignore list are available
-    match (record.target(), &*config.filter_ignore) {
+    match (metadata.target(), &*config.filter_ignore) {
         (path, ignore) if !ignore.is_empty() => {
             // Check that the module path does not match any ignore filters
-            if ignore.iter().any(|v| path.starts_with(&**v)) {
+            if ignore.iter().any(|v| filter_matches(v, path)) {
                 // If not, skip any further writing
                 return true;
             }
@@ -289,5 +1061,665 @@ pub fn should_skip(config: &Config, record: &Record<'_>) -> bool {
         _ => {}
     }
 
+    let path = metadata.target();
+
+    // Check the level of the most specific matching level filter
+    if let Some((_, level)) = config
+        .filter_level
+        .iter()
+        .filter(|(v, _)| filter_matches(v, path))
+        .max_by_key(|(v, _)| v.len())
+    {
+        if metadata.level() > *level {
+            return true;
+        }
+    }
+
+    // Check the custom predicate last, it might be the most expensive
+    if let Some(filter) = &config.filter {
+        if !(filter.0)(metadata) {
+            return true;
+        }
+    }
+
     false
 }
+
+/// Returns if the target starts with the filter, where `*` in the filter matches any sequence
+pub(crate) fn filter_matches(filter: &str, target: &str) -> bool {
+    if !filter.contains('*') {
+        return target.starts_with(filter);
+    }
+
+    let (filter, target) = (filter.as_bytes(), target.as_bytes());
+    let (mut f, mut t) = (0, 0);
+    // position after the last `*` and the target position it currently stands for
+    let mut backtrack = None;
+    while f < filter.len() {
+        if filter[f] == b'*' {
+            f += 1;
+            backtrack = Some((f, t));
+        } else if t < target.len() && filter[f] == target[t] {
+            f += 1;
+            t += 1;
+        } else {
+            match backtrack {
+                Some((star_f, star_t)) if star_t < target.len() => {
+                    f = star_f;
+                    t = star_t + 1;
+                    backtrack = Some((star_f, t));
+                }
+                _ => return false,
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        abbreviate_target, filter_matches, should_skip, truncate_target, try_log, unix_timestamp,
+        write_time, AnsiStripper, Delta,
+    };
+    use crate::{Config, ConfigBuilder, LevelFilter, MultiLineMode};
+    use crate::{TargetAbbreviation, TargetTruncation, UnixPrecision};
+    use log::{Level, Record};
+    use std::borrow::Cow;
+    use std::io::Write;
+    use std::time::Duration;
+
+    #[test]
+    fn strip_ansi() {
+        let mut out = Vec::new();
+        {
+            let mut stripper = AnsiStripper::new(&mut out);
+            write!(
+                stripper,
+                "\x1b[31mred\x1b[0m \x1b]8;;file:///a\x1b\\link\x1b]8;;\x07 plain"
+            )
+            .unwrap();
+            // sequences split across writes
+            stripper.write_all(b"\x1b[1").unwrap();
+            stripper.write_all(b";33m!").unwrap();
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), "red link plain!");
+    }
+
+    #[test]
+    fn target_abbreviation() {
+        let target = "my_crate::network::my_mod";
+        assert_eq!(
+            abbreviate_target(target, TargetAbbreviation::Off),
+            "my_crate::network::my_mod"
+        );
+        assert_eq!(
+            abbreviate_target(target, TargetAbbreviation::LastSegments(2)),
+            "network::my_mod"
+        );
+        assert_eq!(
+            abbreviate_target(target, TargetAbbreviation::LastSegments(5)),
+            "my_crate::network::my_mod"
+        );
+        assert_eq!(
+            abbreviate_target(target, TargetAbbreviation::FirstLetters),
+            "m::n::my_mod"
+        );
+        assert_eq!(
+            abbreviate_target("my_crate", TargetAbbreviation::FirstLetters),
+            "my_crate"
+        );
+    }
+
+    #[test]
+    fn target_truncation() {
+        let truncate = |target, width, truncation| {
+            truncate_target(Cow::Borrowed(target), width, truncation).into_owned()
+        };
+        assert_eq!(
+            truncate("my_crate::db", 8, TargetTruncation::Off),
+            "my_crate::db"
+        );
+        assert_eq!(
+            truncate("my_crate", 8, TargetTruncation::End("…")),
+            "my_crate"
+        );
+        assert_eq!(
+            truncate("my_crate::db", 8, TargetTruncation::End("…")),
+            "my_crat…"
+        );
+        assert_eq!(
+            truncate("my_crate::db", 8, TargetTruncation::Start("..")),
+            "..te::db"
+        );
+        assert_eq!(
+            truncate("my_crate::db", 2, TargetTruncation::Start("...")),
+            "db"
+        );
+    }
+
+    #[test]
+    fn escape_control_chars() {
+        let config = ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_escape_control_chars(true)
+            .build();
+        let mut out = Vec::new();
+        try_log(
+            &config,
+            &Record::builder()
+                .level(Level::Info)
+                .args(format_args!("a\nb\r\x1b[31m\tc\u{85}\u{2028}"))
+                .build(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[INFO] a\\nb\\r\\x1b[31m\tc\\x85\\u{2028}\n"
+        );
+    }
+
+    #[test]
+    fn multi_line_mode() {
+        let log = |mode| {
+            let config = ConfigBuilder::new()
+                .set_time_level(LevelFilter::Off)
+                .set_multi_line_mode(mode)
+                .build();
+            let mut out = Vec::new();
+            try_log(
+                &config,
+                &Record::builder()
+                    .level(Level::Warn)
+                    .args(format_args!("one\ntwo"))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(log(MultiLineMode::Off), "[WARN] one\ntwo\n");
+        assert_eq!(log(MultiLineMode::Indent), "[WARN] one\n       two\n");
+        assert_eq!(log(MultiLineMode::RepeatHeader), "[WARN] one\n[WARN] two\n");
+    }
+
+    #[test]
+    fn location() {
+        let log = |config: &mut ConfigBuilder| {
+            let config = config
+                .set_time_level(LevelFilter::Off)
+                .set_level_padding(crate::LevelPadding::Off)
+                .set_location_level(LevelFilter::Error)
+                .build();
+            let mut out = Vec::new();
+            try_log(
+                &config,
+                &Record::builder()
+                    .level(Level::Error)
+                    .file(Some("src/net/client.rs"))
+                    .line(Some(42))
+                    .args(format_args!("msg"))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(
+            log(&mut ConfigBuilder::new()),
+            "[ERROR] [src/net/client.rs:42] msg\n"
+        );
+        assert_eq!(
+            log(ConfigBuilder::new()
+                .set_location_basename(true)
+                .set_location_separator("#L")),
+            "[ERROR] [client.rs#L42] msg\n"
+        );
+        assert_eq!(
+            log(ConfigBuilder::new().set_location_line(false)),
+            "[ERROR] [src/net/client.rs] msg\n"
+        );
+    }
+
+    #[test]
+    fn module_on_target_override() {
+        let log = |target| {
+            let config = ConfigBuilder::new()
+                .set_time_level(LevelFilter::Off)
+                .set_target_level(LevelFilter::Error)
+                .set_module_level(LevelFilter::Error)
+                .set_module_only_on_target_override(true)
+                .build();
+            let mut out = Vec::new();
+            try_log(
+                &config,
+                &Record::builder()
+                    .level(Level::Error)
+                    .target(target)
+                    .module_path(Some("my_crate::db"))
+                    .args(format_args!("msg"))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(log("my_crate::db"), "[ERROR] my_crate::db: msg\n");
+        assert_eq!(log("audit"), "[ERROR] audit: [my_crate::db] msg\n");
+    }
+
+    #[test]
+    fn uptime() {
+        let config = ConfigBuilder::new().set_time_format_uptime(3).build();
+        let mut out = Vec::new();
+        write_time(&mut out, &config).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("[    "), "{}", out);
+        assert_eq!(out.len(), "[    0.000] ".len());
+    }
+
+    #[test]
+    fn delta() {
+        let delta = |nanos| Delta(Duration::from_nanos(nanos)).to_string();
+        assert_eq!(delta(870), "+0.9µs");
+        assert_eq!(delta(12_340_000), "+12.3ms");
+        assert_eq!(delta(2_500_000_000), "+2.500s");
+    }
+
+    #[test]
+    fn unix_timestamps() {
+        let time = time::macros::datetime!(2023-11-14 22:13:20.123456 UTC);
+        assert_eq!(unix_timestamp(time, UnixPrecision::Seconds), 1_700_000_000);
+        assert_eq!(
+            unix_timestamp(time, UnixPrecision::Millis),
+            1_700_000_000_123
+        );
+        assert_eq!(
+            unix_timestamp(time, UnixPrecision::Micros),
+            1_700_000_000_123_456
+        );
+    }
+
+    #[test]
+    fn sequence() {
+        let config = ConfigBuilder::new()
+            .set_sequence_level(LevelFilter::Error)
+            .set_time_level(LevelFilter::Off)
+            .build();
+        let mut out = Vec::new();
+        for _ in 0..2 {
+            try_log(
+                &config,
+                &Record::builder()
+                    .level(Level::Info)
+                    .args(format_args!("msg"))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "#000000 [INFO] msg\n#000001 [INFO] msg\n"
+        );
+    }
+
+    #[test]
+    fn time_cache() {
+        let config = ConfigBuilder::new()
+            .set_time_format_custom(time::macros::format_description!(
+                "[hour]:[minute]:[second].[subsecond digits:9]"
+            ))
+            .set_time_cache(Some(Duration::from_secs(24 * 60 * 60)))
+            .build();
+        let time = || {
+            let mut out = Vec::new();
+            write_time(&mut out, &config).unwrap();
+            out
+        };
+        let first = time();
+        std::thread::sleep(Duration::from_millis(1));
+        // the subseconds only change, if the day happens to end in between
+        let second = time();
+        assert!(first == second || second == time());
+    }
+
+    #[test]
+    fn presets() {
+        let log = |config: &Config| {
+            let mut out = Vec::new();
+            try_log(
+                config,
+                &Record::builder()
+                    .level(Level::Warn)
+                    .target("my_crate::db")
+                    .args(format_args!("msg"))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(log(&Config::minimal()), "msg\n");
+        assert_eq!(log(&Config::cli()), "[WARN] msg\n");
+        let server = log(&Config::server());
+        assert!(server.contains("Z [WARN ] ("), "{}", server);
+        assert!(server.ends_with(") my_crate::db: msg\n"), "{}", server);
+    }
+
+    #[test]
+    fn prefix() {
+        let config = ConfigBuilder::new()
+            .set_prefix("auth")
+            .set_time_level(LevelFilter::Off)
+            .build();
+        let mut out = Vec::new();
+        try_log(
+            &config,
+            &Record::builder()
+                .level(Level::Info)
+                .args(format_args!("msg"))
+                .build(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "[auth] [INFO] msg\n");
+    }
+
+    #[test]
+    fn glob_filters() {
+        assert!(filter_matches("tokio::uds", "tokio::uds::stream"));
+        assert!(!filter_matches("tokio::uds", "tokio"));
+        assert!(filter_matches("*::tests", "my_crate::db::tests"));
+        assert!(filter_matches("*::tests", "my_crate::tests::helpers"));
+        assert!(!filter_matches("*::tests", "my_crate::db"));
+        assert!(filter_matches("my_crate::*::db", "my_crate::a::b::db"));
+        assert!(!filter_matches("my_crate::*::db", "other::a::db"));
+        assert!(filter_matches("*", "anything"));
+    }
+
+    #[test]
+    fn level_filters() {
+        let config = ConfigBuilder::new()
+            .add_filter_level_str("sqlx", LevelFilter::Warn)
+            .add_filter_level_str("sqlx::query", LevelFilter::Debug)
+            .add_filter_level("hyper::*".to_string(), LevelFilter::Off)
+            .build();
+        let skip = |target: &str, level: Level| {
+            should_skip(
+                &config,
+                &Record::builder()
+                    .target(target)
+                    .level(level)
+                    .args(format_args!(""))
+                    .build(),
+            )
+        };
+        assert!(!skip("sqlx::pool", Level::Warn));
+        assert!(skip("sqlx::pool", Level::Info));
+        assert!(!skip("sqlx::query", Level::Debug));
+        assert!(skip("sqlx::query", Level::Trace));
+        assert!(skip("hyper::proto", Level::Error));
+        assert!(!skip("my_crate", Level::Trace));
+    }
+
+    #[test]
+    fn predicate_filter() {
+        let config = ConfigBuilder::new()
+            .set_filter(Box::new(|metadata| metadata.target() != "skipped"))
+            .build();
+        let skip = |target: &str| {
+            should_skip(
+                &config,
+                &Record::builder()
+                    .target(target)
+                    .args(format_args!(""))
+                    .build(),
+            )
+        };
+        assert!(skip("skipped"));
+        assert!(!skip("logged"));
+        assert!(!should_skip(
+            &config.clone(),
+            &Record::builder().target("logged").build()
+        ));
+    }
+
+    #[cfg(feature = "termcolor")]
+    #[test]
+    fn file_colors() {
+        use crate::{Color, Style};
+
+        let config = ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_write_log_enable_colors(true)
+            .set_level_style(Level::Info, Style::new().fg(Color::Rgb(1, 2, 3)).bold())
+            .set_level_style(Level::Warn, Style::new())
+            .build();
+        let log = |level: Level| {
+            let mut out = Vec::new();
+            try_log(
+                &config,
+                &Record::builder()
+                    .level(level)
+                    .args(format_args!("msg"))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            log(Level::Info),
+            "\x1b[0m\x1b[1m\x1b[38;2;1;2;3m[INFO]\x1b[0m msg\n"
+        );
+        assert_eq!(log(Level::Warn), "[WARN] msg\n");
+    }
+
+    #[test]
+    fn header_alignment() {
+        use crate::HeaderAlignment;
+
+        let log = |config: &Config, level: Level, target: &str| {
+            let mut out = Vec::new();
+            try_log(
+                config,
+                &Record::builder()
+                    .level(level)
+                    .target(target)
+                    .args(format_args!("msg"))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let config = ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_target_level(LevelFilter::Error)
+            .set_header_alignment(HeaderAlignment::Fixed(16))
+            .build();
+        assert_eq!(log(&config, Level::Warn, "app"), "[WARN] app:     msg\n");
+        assert_eq!(
+            log(&config, Level::Error, "app::long::path"),
+            "[ERROR] app::long::path: msg\n"
+        );
+
+        let config = ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_target_level(LevelFilter::Error)
+            .set_header_alignment(HeaderAlignment::Auto)
+            .build();
+        assert_eq!(
+            log(&config, Level::Error, "app::db"),
+            "[ERROR] app::db: msg\n"
+        );
+        assert_eq!(log(&config, Level::Warn, "app"), "[WARN] app:      msg\n");
+    }
+
+    #[cfg(feature = "termcolor")]
+    #[test]
+    fn highlights() {
+        use super::write_args_highlighted;
+        use crate::{Color, Style};
+        use termcolor::Ansi;
+
+        let log = |config: &Config, msg: &str| {
+            let mut out = Ansi::new(Vec::new());
+            write_args_highlighted(
+                &Record::builder().args(format_args!("{}", msg)).build(),
+                &mut out,
+                config,
+                None,
+                None,
+            )
+            .unwrap();
+            String::from_utf8(out.into_inner()).unwrap()
+        };
+
+        let config = ConfigBuilder::new()
+            .add_highlight("timeout", Color::Red)
+            .add_highlight("time", Style::new().bold())
+            .build();
+        assert_eq!(
+            log(&config, "timeout after some time"),
+            "\x1b[0m\x1b[31mtimeout\x1b[0m after some \x1b[0m\x1b[1mtime\x1b[0m\n"
+        );
+        assert_eq!(log(&config, "no match"), "no match\n");
+
+        #[cfg(feature = "regex")]
+        {
+            let config = ConfigBuilder::new()
+                .add_highlight_regex(crate::Regex::new(r"\d+ms").unwrap(), Color::Yellow)
+                .build();
+            assert_eq!(
+                log(&config, "took 250ms"),
+                "took \x1b[0m\x1b[33m250ms\x1b[0m\n"
+            );
+        }
+    }
+
+    #[test]
+    fn enabled_filters() {
+        use crate::{CombinedLogger, WriteLogger};
+        use log::{Log, Metadata};
+
+        let metadata = |target| {
+            Metadata::builder()
+                .level(Level::Debug)
+                .target(target)
+                .build()
+        };
+        let quiet = WriteLogger::new(
+            LevelFilter::Debug,
+            ConfigBuilder::new()
+                .add_filter_ignore_str("heavy")
+                .add_filter_level_str("chatty", LevelFilter::Info)
+                .build(),
+            Vec::new(),
+        );
+        assert!(quiet.enabled(&metadata("app")));
+        assert!(!quiet.enabled(&metadata("heavy::parser")));
+        assert!(!quiet.enabled(&metadata("chatty")));
+
+        let allowed = WriteLogger::new(
+            LevelFilter::Debug,
+            ConfigBuilder::new().add_filter_allow_str("chatty").build(),
+            Vec::new(),
+        );
+        let combined = CombinedLogger::new(vec![quiet, allowed]);
+        assert!(combined.enabled(&metadata("app")));
+        assert!(combined.enabled(&metadata("chatty")));
+        assert!(!combined.enabled(&metadata("heavy")));
+    }
+
+    #[test]
+    fn line_ending() {
+        use crate::LineEnding;
+
+        let expected = if cfg!(windows) { "\r\n" } else { "\n" };
+        assert_eq!(LineEnding::platform().as_str(), expected);
+
+        let config = ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_line_ending(LineEnding::Crlf)
+            .set_escape_control_chars(true)
+            .build();
+        let mut out = Vec::new();
+        for msg in ["first\r\nforged", "second"] {
+            try_log(
+                &config,
+                &Record::builder()
+                    .level(Level::Info)
+                    .args(format_args!("{}", msg))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[INFO] first\\r\\nforged\r\n[INFO] second\r\n"
+        );
+    }
+
+    #[test]
+    fn target_aliases() {
+        let config = ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_target_level(LevelFilter::Error)
+            .add_target_alias_str("my_app::internal", "core")
+            .add_target_alias_str("my_app::internal::db", "storage")
+            .add_target_alias("*::http".to_string(), "web".to_string())
+            .add_filter_ignore_str("my_app::internal::cache")
+            .build();
+        let log = |target: &str| {
+            let mut out = Vec::new();
+            try_log(
+                &config,
+                &Record::builder()
+                    .level(Level::Info)
+                    .target(target)
+                    .args(format_args!("msg"))
+                    .build(),
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(log("my_app::internal::net"), "[INFO] core: msg\n");
+        assert_eq!(log("my_app::internal::db::pool"), "[INFO] storage: msg\n");
+        assert_eq!(log("my_app::api::http"), "[INFO] web: msg\n");
+        assert_eq!(log("my_app::api"), "[INFO] my_app::api: msg\n");
+        // filters still see the original target
+        assert_eq!(log("my_app::internal::cache"), "");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_format() {
+        let record = Record::builder()
+            .level(Level::Info)
+            .args(format_args!("msg"))
+            .build();
+        let config = ConfigBuilder::new()
+            .set_time_format_chrono("%Y-%m-%d %H:%M")
+            .try_build()
+            .unwrap();
+        let mut out = Vec::new();
+        write_time(&mut out, &config).unwrap();
+        assert_eq!(out.len(), "1970-01-01 00:00 ".len());
+
+        // logging with an invalid format fails without panicking
+        let config = ConfigBuilder::new().set_time_format_chrono("%Q").build();
+        let mut out = Vec::new();
+        assert!(try_log(&config, &record, &mut out).is_err());
+    }
+}
@@ -1,16 +1,33 @@
+mod budgetlog;
 mod comblog;
+mod earlylog;
+mod fanoutlog;
+mod heartbeatlog;
+mod install;
 pub mod logging;
+mod proxylog;
 mod simplelog;
+mod splitlog;
 #[cfg(feature = "termcolor")]
 mod termlog;
 #[cfg(feature = "test")]
 mod testlog;
+#[cfg(feature = "tracing")]
+mod tracinglog;
 mod writelog;
 
-pub use self::comblog::CombinedLogger;
+pub use self::budgetlog::BudgetLogger;
+pub use self::comblog::{CombinedChild, CombinedLevelHandle, CombinedLogger};
+pub use self::earlylog::EarlyLogger;
+pub use self::fanoutlog::FanOutLogger;
+pub use self::heartbeatlog::HeartbeatLogger;
+pub use self::proxylog::ProxyLogger;
 pub use self::simplelog::SimpleLogger;
+pub use self::splitlog::SplitLogger;
 #[cfg(feature = "termcolor")]
 pub use self::termlog::{TermLogger, TerminalMode};
 #[cfg(feature = "test")]
-pub use self::testlog::TestLogger;
+pub use self::testlog::{CapturedRecords, TestLogger};
+#[cfg(feature = "tracing")]
+pub use self::tracinglog::TracingLayer;
 pub use self::writelog::WriteLogger;
@@ -0,0 +1,107 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the ProxyLogger Implementation
+
+use super::logging::start_time;
+use crate::SharedLogger;
+use log::{set_logger, set_max_level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::sync::RwLock;
+
+static PROXY_LOGGER: ProxyLogger = ProxyLogger {
+    inner: RwLock::new(None),
+};
+
+/// The ProxyLogger struct. Forwards all records to an exchangeable logger.
+///
+/// The ProxyLogger is installed once through `ProxyLogger::init`, e.g. by a library
+/// setting up its logging infrastructure. The logger actually receiving the records
+/// can then be set and swapped at any time afterwards through `ProxyLogger::set`.
+///
+/// Until a logger is set, all records are discarded.
+pub struct ProxyLogger {
+    inner: RwLock<Option<Box<dyn SharedLogger>>>,
+}
+
+impl ProxyLogger {
+    /// init function. Globally initializes the ProxyLogger as the one and only used log facility.
+    ///
+    /// Fails if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let _ = ProxyLogger::init();
+    /// // later on
+    /// ProxyLogger::set(SimpleLogger::new(LevelFilter::Info, Config::default()));
+    /// # }
+    /// ```
+    pub fn init() -> Result<(), SetLoggerError> {
+        set_logger(&PROXY_LOGGER)?;
+        let level = PROXY_LOGGER.level();
+        set_max_level(level);
+        start_time();
+        Ok(())
+    }
+
+    /// Sets the logger all records are forwarded to, returning the previously set logger.
+    ///
+    /// The global maximum log level is adjusted to the level of the new logger.
+    /// The previous logger is flushed, before it is returned.
+    pub fn set(logger: Box<dyn SharedLogger>) -> Option<Box<dyn SharedLogger>> {
+        let level = logger.level();
+        let previous = PROXY_LOGGER.inner.write().unwrap().replace(logger);
+        set_max_level(level);
+        if let Some(previous) = &previous {
+            previous.flush();
+        }
+        previous
+    }
+
+    /// Removes the logger records are forwarded to, returning it.
+    ///
+    /// Afterwards all records are discarded, until a new logger is set.
+    pub fn take() -> Option<Box<dyn SharedLogger>> {
+        let previous = PROXY_LOGGER.inner.write().unwrap().take();
+        set_max_level(LevelFilter::Off);
+        if let Some(previous) = &previous {
+            previous.flush();
+        }
+        previous
+    }
+
+    fn level(&self) -> LevelFilter {
+        self.inner
+            .read()
+            .unwrap()
+            .as_ref()
+            .map_or(LevelFilter::Off, |logger| logger.level())
+    }
+}
+
+impl Log for ProxyLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        match &*self.inner.read().unwrap() {
+            Some(logger) => logger.enabled(metadata),
+            None => false,
+        }
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if let Some(logger) = &*self.inner.read().unwrap() {
+            logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(logger) = &*self.inner.read().unwrap() {
+            logger.flush();
+        }
+    }
+}
@@ -7,19 +7,19 @@
 
 //! Module providing the SimpleLogger Implementation
 
-use super::logging::try_log;
-use crate::{Config, SharedLogger};
-use log::{
-    set_boxed_logger, set_max_level, Level, LevelFilter, Log, Metadata, Record, SetLoggerError,
-};
-use std::io::{stderr, stdout};
+use super::install::{set_global_logger, tolerate_existing_logger};
+use super::logging::{should_skip_metadata, try_log};
+use crate::{Config, InitError, SharedLogger};
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::io::{stderr, stdout, BufWriter, Stdout, Write};
 use std::sync::Mutex;
 
 /// The SimpleLogger struct. Provides a very basic Logger implementation
 pub struct SimpleLogger {
     level: LevelFilter,
     config: Config,
-    output_lock: Mutex<()>,
+    /// Buffer for stdout, if created with `new_buffered`
+    output_lock: Mutex<Option<BufWriter<Stdout>>>,
 }
 
 impl SimpleLogger {
@@ -37,8 +37,44 @@ impl SimpleLogger {
     /// # }
     /// ```
     pub fn init(log_level: LevelFilter, config: Config) -> Result<(), SetLoggerError> {
-        set_max_level(log_level);
-        set_boxed_logger(SimpleLogger::new(log_level, config))
+        set_global_logger(SimpleLogger::new(log_level, config))
+    }
+
+    /// Like `init`, but tolerates an already initialized Logger.
+    ///
+    /// Returns `Ok(true)`, if the SimpleLogger was set as the global log facility,
+    /// and `Ok(false)`, if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// match SimpleLogger::try_init(LevelFilter::Info, Config::default()) {
+    ///     Ok(true) => {}
+    ///     Ok(false) => println!("logging was already set up"),
+    ///     Err(err) => println!("failed to set up logging: {}", err),
+    /// }
+    /// # }
+    /// ```
+    pub fn try_init(log_level: LevelFilter, config: Config) -> Result<bool, InitError> {
+        tolerate_existing_logger(SimpleLogger::init(log_level, config))
+    }
+
+    /// Like `init`, but buffers the output written to stdout (see `new_buffered`).
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let _ = SimpleLogger::init_buffered(LevelFilter::Info, Config::default());
+    /// // ...
+    /// simplelog::shutdown();
+    /// # }
+    /// ```
+    pub fn init_buffered(log_level: LevelFilter, config: Config) -> Result<(), SetLoggerError> {
+        set_global_logger(SimpleLogger::new_buffered(log_level, config))
     }
 
     /// allows to create a new logger, that can be independently used, no matter what is globally set.
@@ -61,27 +97,58 @@ impl SimpleLogger {
         Box::new(SimpleLogger {
             level: log_level,
             config,
-            output_lock: Mutex::new(()),
+            output_lock: Mutex::new(None),
+        })
+    }
+
+    /// Like `new`, but buffers the output written to stdout instead of writing every line directly,
+    /// which speeds up programs logging lots of records.
+    ///
+    /// The buffer is flushed before an error is written to stderr and when the logger is flushed,
+    /// so make sure to call [`shutdown`](crate::shutdown) before exiting to not lose the last records.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let simple_logger = SimpleLogger::new_buffered(LevelFilter::Info, Config::default());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new_buffered(log_level: LevelFilter, config: Config) -> Box<SimpleLogger> {
+        Box::new(SimpleLogger {
+            level: log_level,
+            config,
+            output_lock: Mutex::new(Some(BufWriter::new(stdout()))),
         })
     }
 }
 
 impl Log for SimpleLogger {
     fn enabled(&self, metadata: &Metadata<'_>) -> bool {
-        metadata.level() <= self.level
+        metadata.level() <= self.level && !should_skip_metadata(&self.config, metadata)
     }
 
     fn log(&self, record: &Record<'_>) {
-        if self.enabled(record.metadata()) {
-            let _lock = self.output_lock.lock().unwrap();
+        // the filters are checked while logging the record
+        if record.level() <= self.level {
+            let mut buffer = self.output_lock.lock().unwrap();
 
-            match record.level() {
-                Level::Error => {
+            match (record.level(), buffer.as_mut()) {
+                (Level::Error, buffer) => {
+                    // keep the order of the lines, if both streams go to the same terminal
+                    if let Some(buffer) = buffer {
+                        let _ = buffer.flush();
+                    }
                     let stderr = stderr();
                     let mut stderr_lock = stderr.lock();
                     let _ = try_log(&self.config, record, &mut stderr_lock);
                 }
-                _ => {
+                (_, Some(buffer)) => {
+                    let _ = try_log(&self.config, record, buffer);
+                }
+                (_, None) => {
                     let stdout = stdout();
                     let mut stdout_lock = stdout.lock();
                     let _ = try_log(&self.config, record, &mut stdout_lock);
@@ -91,7 +158,9 @@ impl Log for SimpleLogger {
     }
 
     fn flush(&self) {
-        use std::io::Write;
+        if let Some(buffer) = self.output_lock.lock().unwrap().as_mut() {
+            let _ = buffer.flush();
+        }
         let _ = stdout().flush();
     }
 }
@@ -108,4 +177,46 @@ impl SharedLogger for SimpleLogger {
     fn as_log(self: Box<Self>) -> Box<dyn Log> {
         Box::new(*self)
     }
+
+    fn name(&self) -> &'static str {
+        "SimpleLogger"
+    }
+
+    fn sink_description(&self) -> String {
+        String::from("stdout, errors to stderr")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Config, SimpleLogger};
+    use log::{Level, LevelFilter, Log, Record};
+
+    #[test]
+    fn buffered_until_flush() {
+        let logger = SimpleLogger::new_buffered(LevelFilter::Info, Config::minimal());
+        let pending = || {
+            let buffer = logger.output_lock.lock().unwrap();
+            String::from_utf8(buffer.as_ref().unwrap().buffer().to_vec()).unwrap()
+        };
+        let log = |level: Level| {
+            logger.log(
+                &Record::builder()
+                    .level(level)
+                    .args(format_args!("buffered {}", level))
+                    .build(),
+            )
+        };
+
+        log(Level::Info);
+        log(Level::Warn);
+        assert_eq!(pending(), "buffered INFO\nbuffered WARN\n");
+        logger.flush();
+        assert_eq!(pending(), "");
+
+        log(Level::Info);
+        // errors go to stderr, after the buffered lines
+        log(Level::Error);
+        assert_eq!(pending(), "");
+    }
 }
@@ -0,0 +1,204 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the SplitLogger Implementation
+
+use super::install::{set_global_logger, tolerate_existing_logger};
+use crate::{Config, InitError, SharedLogger};
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::ops::RangeInclusive;
+
+/// The SplitLogger struct. Provides a Logger implementation routing records to different loggers by their level.
+///
+/// Every logger is registered with a range of levels and receives all records within that range,
+/// e.g. `Level::Error..=Level::Warn` for warnings and errors or `Level::Info..=Level::Trace`
+/// for everything below warnings. Ranges may overlap, records are passed to every matching logger.
+pub struct SplitLogger {
+    level: LevelFilter,
+    sinks: Vec<(RangeInclusive<Level>, Box<dyn SharedLogger>)>,
+}
+
+impl SplitLogger {
+    /// init function. Globally initializes the SplitLogger as the one and only used log facility.
+    ///
+    /// Takes the loggers with their range of levels as argument.
+    /// Fails if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::fs::File;
+    /// # fn main() {
+    /// let _ = SplitLogger::init(vec![
+    ///     (Level::Error..=Level::Warn, WriteLogger::new(LevelFilter::Trace, Config::default(), std::io::stderr())),
+    ///     (Level::Error..=Level::Info, WriteLogger::new(LevelFilter::Trace, Config::default(), File::create("my_rust_bin.log").unwrap())),
+    /// ]);
+    /// # }
+    /// ```
+    pub fn init(
+        sinks: Vec<(RangeInclusive<Level>, Box<dyn SharedLogger>)>,
+    ) -> Result<(), SetLoggerError> {
+        set_global_logger(SplitLogger::new(sinks))
+    }
+
+    /// Like `init`, but tolerates an already initialized Logger.
+    ///
+    /// Returns `Ok(true)`, if the SplitLogger was set as the global log facility,
+    /// and `Ok(false)`, if another Logger was already initialized.
+    pub fn try_init(
+        sinks: Vec<(RangeInclusive<Level>, Box<dyn SharedLogger>)>,
+    ) -> Result<bool, InitError> {
+        tolerate_existing_logger(SplitLogger::init(sinks))
+    }
+
+    /// allows to create a new logger, that can be independently used, no matter what is globally set.
+    ///
+    /// no macros are provided for this case and you probably
+    /// dont want to use this function, but `init()`, if you dont want to build a `CombinedLogger`.
+    ///
+    /// Takes the loggers with their range of levels as argument. The log level is automatically
+    /// determined by the most verbose level any of the loggers accepts within its range.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let split_logger = SplitLogger::new(vec![
+    ///     (Level::Error..=Level::Warn, WriteLogger::new(LevelFilter::Trace, Config::default(), std::io::stderr())),
+    ///     (Level::Info..=Level::Trace, WriteLogger::new(LevelFilter::Debug, Config::default(), std::io::stdout())),
+    /// ]);
+    /// assert_eq!(split_logger.level(), LevelFilter::Debug);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new(sinks: Vec<(RangeInclusive<Level>, Box<dyn SharedLogger>)>) -> Box<SplitLogger> {
+        let level = sinks
+            .iter()
+            .filter(|(levels, _)| !levels.is_empty())
+            .map(|(levels, logger)| logger.level().min(levels.end().to_level_filter()))
+            .max()
+            .unwrap_or(LevelFilter::Off);
+
+        Box::new(SplitLogger { level, sinks })
+    }
+
+    /// Returns the loggers with their range of levels
+    pub fn sinks(&self) -> &[(RangeInclusive<Level>, Box<dyn SharedLogger>)] {
+        &self.sinks
+    }
+}
+
+impl Log for SplitLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+            && self.sinks.iter().any(|(levels, logger)| {
+                levels.contains(&metadata.level()) && logger.enabled(metadata)
+            })
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        // the loggers check their filters themselves
+        if record.level() <= self.level {
+            for (levels, logger) in &self.sinks {
+                if levels.contains(&record.level()) {
+                    logger.log(record);
+                }
+            }
+        }
+    }
+
+    fn flush(&self) {
+        for (_, logger) in &self.sinks {
+            logger.flush();
+        }
+    }
+}
+
+impl SharedLogger for SplitLogger {
+    fn level(&self) -> LevelFilter {
+        self.level
+    }
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn Log> {
+        Box::new(*self)
+    }
+
+    fn name(&self) -> &'static str {
+        "SplitLogger"
+    }
+
+    fn sink_description(&self) -> String {
+        self.sinks
+            .iter()
+            .map(|(_, logger)| logger)
+            .map(|logger| format!("{}: {}", logger.name(), logger.sink_description()))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    fn records_written(&self) -> Option<u64> {
+        self.sinks
+            .iter()
+            .map(|(_, logger)| logger)
+            .filter_map(|logger| logger.records_written())
+            .reduce(|a, b| a + b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ConfigBuilder;
+    use log::{Level, LevelFilter, Record};
+
+    #[test]
+    fn split_by_level() {
+        use crate::{SharedLogger, SplitLogger, WriteLogger};
+        use log::Log;
+
+        let config = || {
+            ConfigBuilder::new()
+                .set_time_level(LevelFilter::Off)
+                .build()
+        };
+        let split = SplitLogger::new(vec![
+            (
+                Level::Error..=Level::Warn,
+                WriteLogger::new(LevelFilter::Trace, config(), Vec::new()),
+            ),
+            (
+                Level::Info..=Level::Trace,
+                WriteLogger::new(LevelFilter::Info, config(), Vec::new()),
+            ),
+        ]);
+        assert_eq!(split.level(), LevelFilter::Info);
+        for level in [Level::Error, Level::Warn, Level::Info] {
+            split.log(
+                &Record::builder()
+                    .level(level)
+                    .args(format_args!("msg"))
+                    .build(),
+            );
+        }
+
+        let output = |index: usize| {
+            split.sinks()[index]
+                .1
+                .as_any()
+                .downcast_ref::<WriteLogger<Vec<u8>>>()
+                .unwrap()
+                .with_writer(|w| String::from_utf8(w.clone()).unwrap())
+        };
+        assert_eq!(output(0), "[ERROR] msg\n[WARN] msg\n");
+        assert_eq!(output(1), "[INFO] msg\n");
+    }
+}
@@ -1,21 +1,22 @@
 //! Module providing the TermLogger Implementation
 
-use log::{
-    set_boxed_logger, set_max_level, Level, LevelFilter, Log, Metadata, Record, SetLoggerError,
-};
-use std::io::{Error, Write};
-use std::sync::Mutex;
-use termcolor::{BufferedStandardStream, ColorChoice};
-#[cfg(not(feature = "ansi_term"))]
-use termcolor::{ColorSpec, WriteColor};
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::env;
+use std::io::{self, Error, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use termcolor::{Buffer, BufferedStandardStream, ColorChoice, ColorSpec, WriteColor};
 
+use super::install::{set_global_logger, tolerate_existing_logger};
 use super::logging::*;
 
-use crate::{Config, SharedLogger, ThreadLogMode};
+use crate::{
+    Config, HeaderAlignment, InitError, MultiLineMode, SharedLogger, Style, ThreadLogMode,
+};
 
 struct OutputStreams {
-    err: BufferedStandardStream,
-    out: BufferedStandardStream,
+    err: Box<dyn WriteColor + Send>,
+    out: Box<dyn WriteColor + Send>,
 }
 
 /// Specifies which streams should be used when logging
@@ -29,6 +30,7 @@ pub enum TerminalMode {
     Mixed,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for TerminalMode {
     fn default() -> TerminalMode {
         TerminalMode::Mixed
@@ -38,10 +40,24 @@ impl Default for TerminalMode {
 /// The TermLogger struct. Provides a stderr/out based Logger implementation
 ///
 /// Supports colored output
+///
+/// With the `kv` feature, a record carrying a `color` key-value (e.g. `info!(color = "green"; "build finished")`)
+/// is printed in that color as a whole, without misusing a level for it.
+/// The value takes the names and formats accepted by `termcolor::Color`, like `green`, `208` or `255,128,0`.
 pub struct TermLogger {
     level: LevelFilter,
     config: Config,
     streams: Mutex<OutputStreams>,
+    sink: &'static str,
+    /// The error stream is a terminal, see `ConfigBuilder::set_error_attention`
+    #[cfg(feature = "console-attention")]
+    interactive: bool,
+    /// Ignores the flush level, e.g. because every flush hands a line to a print hook
+    flush_every_record: bool,
+    /// Directory relative source files are resolved against, see `ConfigBuilder::set_location_hyperlink`
+    current_dir: Option<PathBuf>,
+    /// The streams are terminals, so source locations may be rendered as hyperlinks
+    hyperlinks: bool,
 }
 
 impl TermLogger {
@@ -69,10 +85,38 @@ impl TermLogger {
         mode: TerminalMode,
         color_choice: ColorChoice,
     ) -> Result<(), SetLoggerError> {
-        let logger = TermLogger::new(log_level, config, mode, color_choice);
-        set_max_level(log_level);
-        set_boxed_logger(logger)?;
-        Ok(())
+        set_global_logger(TermLogger::new(log_level, config, mode, color_choice))
+    }
+
+    /// Like `init`, but tolerates an already initialized Logger.
+    ///
+    /// Returns `Ok(true)`, if the TermLogger was set as the global log facility,
+    /// and `Ok(false)`, if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// match TermLogger::try_init(
+    ///     LevelFilter::Info,
+    ///     Config::default(),
+    ///     TerminalMode::Mixed,
+    ///     ColorChoice::Auto
+    /// ) {
+    ///     Ok(true) => {}
+    ///     Ok(false) => println!("logging was already set up"),
+    ///     Err(err) => println!("failed to set up logging: {}", err),
+    /// }
+    /// # }
+    /// ```
+    pub fn try_init(
+        log_level: LevelFilter,
+        config: Config,
+        mode: TerminalMode,
+        color_choice: ColorChoice,
+    ) -> Result<bool, InitError> {
+        tolerate_existing_logger(TermLogger::init(log_level, config, mode, color_choice))
     }
 
     /// allows to create a new logger, that can be independently used, no matter whats globally set.
@@ -104,105 +148,316 @@ impl TermLogger {
         mode: TerminalMode,
         color_choice: ColorChoice,
     ) -> Box<TermLogger> {
-        let streams = match mode {
-            TerminalMode::Stdout => OutputStreams {
-                err: BufferedStandardStream::stdout(color_choice),
-                out: BufferedStandardStream::stdout(color_choice),
-            },
-            TerminalMode::Stderr => OutputStreams {
-                err: BufferedStandardStream::stderr(color_choice),
-                out: BufferedStandardStream::stderr(color_choice),
-            },
-            TerminalMode::Mixed => OutputStreams {
-                err: BufferedStandardStream::stderr(color_choice),
-                out: BufferedStandardStream::stdout(color_choice),
-            },
+        let (out, err) = match mode {
+            TerminalMode::Stdout => (
+                BufferedStandardStream::stdout(color_choice),
+                BufferedStandardStream::stdout(color_choice),
+            ),
+            TerminalMode::Stderr => (
+                BufferedStandardStream::stderr(color_choice),
+                BufferedStandardStream::stderr(color_choice),
+            ),
+            TerminalMode::Mixed => (
+                BufferedStandardStream::stdout(color_choice),
+                BufferedStandardStream::stderr(color_choice),
+            ),
+        };
+
+        let mut logger = TermLogger::with_streams(log_level, config, Box::new(out), Box::new(err));
+        logger.sink = match mode {
+            TerminalMode::Stdout => "stdout",
+            TerminalMode::Stderr => "stderr",
+            TerminalMode::Mixed => "stdout, errors to stderr",
         };
+        logger.hyperlinks = logger.config.location_hyperlink.is_some()
+            && match mode {
+                TerminalMode::Stdout => io::stdout().is_terminal(),
+                TerminalMode::Stderr => io::stderr().is_terminal(),
+                TerminalMode::Mixed => io::stdout().is_terminal() && io::stderr().is_terminal(),
+            };
+        #[cfg(feature = "console-attention")]
+        {
+            logger.interactive = match mode {
+                TerminalMode::Stdout => io::stdout().is_terminal(),
+                TerminalMode::Stderr | TerminalMode::Mixed => io::stderr().is_terminal(),
+            };
+        }
+        logger
+    }
 
+    /// allows to create a new logger writing to the given streams instead of stdout/stderr.
+    ///
+    /// Error records are written to `err`, all other records to `out`.
+    /// Useful to capture the colored output in tests or to redirect it e.g. into a TUI.
+    ///
+    /// Takes the desired `Level` and `Config` as arguments. They cannot be changed later on.
+    ///
+    /// Returns a `Box`ed TermLogger
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use termcolor::Buffer;
+    /// # fn main() {
+    /// let term_logger = TermLogger::with_streams(
+    ///     LevelFilter::Info,
+    ///     Config::default(),
+    ///     Box::new(Buffer::ansi()),
+    ///     Box::new(Buffer::ansi()),
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_streams(
+        log_level: LevelFilter,
+        config: Config,
+        out: Box<dyn WriteColor + Send>,
+        err: Box<dyn WriteColor + Send>,
+    ) -> Box<TermLogger> {
+        let current_dir = config
+            .location_hyperlink
+            .as_ref()
+            .and_then(|_| env::current_dir().ok());
         Box::new(TermLogger {
             level: log_level,
             config,
-            streams: Mutex::new(streams),
+            streams: Mutex::new(OutputStreams { err, out }),
+            sink: "custom streams",
+            #[cfg(feature = "console-attention")]
+            interactive: false,
+            flush_every_record: false,
+            current_dir,
+            hyperlinks: false,
         })
     }
 
-    fn try_log_term(
+    /// allows to create a new logger handing every formatted line to the given hook,
+    /// instead of writing it to stdout/stderr.
+    ///
+    /// The line passed to the hook does not contain the line ending and contains
+    /// ANSI color sequences, unless `color_choice` is `ColorChoice::Never`.
+    /// This allows routing the output e.g. through `indicatif::ProgressBar::println`,
+    /// so log lines don't mangle active progress bars.
+    ///
+    /// Takes the desired `Level` and `Config` as arguments. They cannot be changed later on.
+    ///
+    /// Returns a `Box`ed TermLogger
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let term_logger = TermLogger::with_print_hook(
+    ///     LevelFilter::Info,
+    ///     Config::default(),
+    ///     ColorChoice::Auto,
+    ///     Box::new(|line| println!("{}", line)),
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_print_hook(
+        log_level: LevelFilter,
+        config: Config,
+        color_choice: ColorChoice,
+        hook: Box<dyn Fn(&str) + Send + Sync>,
+    ) -> Box<TermLogger> {
+        let hook: Arc<dyn Fn(&str) + Send + Sync> = Arc::from(hook);
+        let mut logger = TermLogger::with_streams(
+            log_level,
+            config,
+            Box::new(PrintHookStream::new(color_choice, hook.clone())),
+            Box::new(PrintHookStream::new(color_choice, hook)),
+        );
+        logger.sink = "print hook";
+        logger.flush_every_record = true;
+        logger
+    }
+
+    fn try_log_term<W>(&self, record: &Record<'_>, term_lock: &mut W) -> Result<(), Error>
+    where
+        W: WriteColor + Sized,
+    {
+        let color = color_override(record).map(Style::from);
+        let colorize_line = (color.is_some() || record.level() <= self.config.colorize_line)
+            && !self.config.write_log_enable_colors;
+
+        let line_style = color
+            .as_ref()
+            .unwrap_or(&self.config.level_style[record.level() as usize]);
+        let line_style = if colorize_line {
+            term_lock.set_color(&line_style.to_color_spec())?;
+            Some(line_style)
+        } else {
+            None
+        };
+
+        if self.config.multi_line_mode == MultiLineMode::Off {
+            self.write_header_term(record, term_lock, colorize_line)?;
+            write_args_highlighted(record, term_lock, &self.config, None, line_style)?;
+        } else {
+            let mut recorder = HeaderRecorder::new(term_lock);
+            self.write_header_term(record, &mut recorder, colorize_line)?;
+            let header = recorder.finish();
+            write_args_highlighted(
+                record,
+                term_lock,
+                &self.config,
+                Some(&mut |term_lock: &mut W| {
+                    header.write_continuation_color(term_lock, self.config.multi_line_mode)
+                }),
+                line_style,
+            )?;
+        }
+
+        if colorize_line {
+            term_lock.reset()?;
+        }
+
+        // The log crate holds the logger as a `static mut`, which isn't dropped
+        // at program exit: https://doc.rust-lang.org/reference/items/static-items.html
+        // Sadly, this means we can't rely on the BufferedStandardStreams flushing
+        // themselves on the way out, so to avoid the Case of the Missing 8k,
+        // flush each entry, unless asked to only flush severe records.
+        // The rest gets flushed by `Log::flush`, e.g. through `shutdown`.
+        if self.flush_every_record || record.level() <= self.config.flush_level {
+            term_lock.flush()?;
+        }
+        Ok(())
+    }
+
+    fn write_header_term<W>(
+        &self,
+        record: &Record<'_>,
+        term_lock: &mut W,
+        colorize_line: bool,
+    ) -> Result<(), Error>
+    where
+        W: WriteColor + Sized,
+    {
+        if self.config.header_alignment == HeaderAlignment::Off {
+            return self.write_header_parts_term(record, term_lock, colorize_line);
+        }
+
+        let mut counter = WidthCounter::new(term_lock);
+        self.write_header_parts_term(record, &mut counter, colorize_line)?;
+        let padding = self.config.header_padding(counter.width());
+        write!(term_lock, "{:1$}", "", padding)
+    }
+
+    fn write_header_parts_term<W>(
         &self,
         record: &Record<'_>,
-        term_lock: &mut BufferedStandardStream,
-    ) -> Result<(), Error> {
-        #[cfg(not(feature = "ansi_term"))]
-        let color = self.config.level_color[record.level() as usize];
+        term_lock: &mut W,
+        colorize_line: bool,
+    ) -> Result<(), Error>
+    where
+        W: WriteColor + Sized,
+    {
+        let style = self.config.level_style[record.level() as usize];
+
+        if let Some(prefix) = &self.config.prefix {
+            write_prefix(term_lock, prefix)?;
+        }
+
+        if self.config.sequence <= record.level() && self.config.sequence != LevelFilter::Off {
+            write_sequence(term_lock, &self.config)?;
+        }
 
         if self.config.time <= record.level() && self.config.time != LevelFilter::Off {
-            write_time(term_lock, &self.config)?;
+            write_styled(
+                term_lock,
+                &self.config.time_style,
+                colorize_line,
+                |term_lock| write_time(term_lock, &self.config),
+            )?;
         }
 
-        if self.config.level <= record.level() && self.config.level != LevelFilter::Off {
-            #[cfg(not(feature = "ansi_term"))]
-            if !self.config.write_log_enable_colors {
-                term_lock.set_color(ColorSpec::new().set_fg(color))?;
-            }
+        if self.config.hostname <= record.level() && self.config.hostname != LevelFilter::Off {
+            write_hostname(term_lock, &self.config)?;
+        }
 
-            write_level(record, term_lock, &self.config)?;
+        if self.config.pid <= record.level() && self.config.pid != LevelFilter::Off {
+            write_pid(term_lock)?;
+        }
 
-            #[cfg(not(feature = "ansi_term"))]
-            if !self.config.write_log_enable_colors {
-                term_lock.reset()?;
+        if self.config.level <= record.level() && self.config.level != LevelFilter::Off {
+            if self.config.write_log_enable_colors {
+                write_level(record, term_lock, &self.config)?;
+            } else {
+                write_styled(term_lock, &style, colorize_line, |term_lock| {
+                    write_level(record, term_lock, &self.config)
+                })?;
             }
         }
 
         if self.config.thread <= record.level() && self.config.thread != LevelFilter::Off {
-            match self.config.thread_log_mode {
-                ThreadLogMode::IDs => {
-                    write_thread_id(term_lock, &self.config)?;
-                }
-                ThreadLogMode::Names | ThreadLogMode::Both => {
-                    write_thread_name(term_lock, &self.config)?;
-                }
-            }
+            write_styled(
+                term_lock,
+                &self.config.thread_style,
+                colorize_line,
+                |term_lock| match self.config.thread_log_mode {
+                    ThreadLogMode::IDs => write_thread_id(term_lock, &self.config),
+                    ThreadLogMode::Names | ThreadLogMode::Both => {
+                        write_thread_name(term_lock, &self.config)
+                    }
+                },
+            )?;
         }
 
         if self.config.target <= record.level() && self.config.target != LevelFilter::Off {
-            write_target(record, term_lock, &self.config)?;
+            write_styled(
+                term_lock,
+                &self.config.target_style,
+                colorize_line,
+                |term_lock| write_target(record, term_lock, &self.config),
+            )?;
         }
 
         if self.config.location <= record.level() && self.config.location != LevelFilter::Off {
-            write_location(record, term_lock)?;
+            match &self.config.location_hyperlink {
+                Some(template) if self.hyperlinks && term_lock.supports_color() => {
+                    write_location_hyperlink(
+                        record,
+                        term_lock,
+                        &self.config,
+                        template,
+                        self.current_dir.as_deref(),
+                    )?;
+                }
+                _ => write_location(record, term_lock, &self.config)?,
+            }
         }
 
-        if self.config.module <= record.level() && self.config.module != LevelFilter::Off {
+        if self.config.module <= record.level()
+            && self.config.module != LevelFilter::Off
+            && !(self.config.module_only_on_target_override
+                && record.module_path() == Some(record.target()))
+        {
             write_module(record, term_lock)?;
         }
 
-        #[cfg(feature = "paris")]
-        write_args(
-            record,
-            term_lock,
-            self.config.enable_paris_formatting,
-            &self.config.line_ending,
-        )?;
-        #[cfg(not(feature = "paris"))]
-        write_args(record, term_lock, &self.config.line_ending)?;
-
-        // The log crate holds the logger as a `static mut`, which isn't dropped
-        // at program exit: https://doc.rust-lang.org/reference/items/static-items.html
-        // Sadly, this means we can't rely on the BufferedStandardStreams flushing
-        // themselves on the way out, so to avoid the Case of the Missing 8k,
-        // flush each entry.
-        term_lock.flush()
+        Ok(())
     }
 
     fn try_log(&self, record: &Record<'_>) -> Result<(), Error> {
-        if self.enabled(record.metadata()) {
+        if record.level() <= self.level {
             if should_skip(&self.config, record) {
                 return Ok(());
             }
+            self.config.count_record();
 
             let mut streams = self.streams.lock().unwrap();
 
             if record.level() == Level::Error {
-                self.try_log_term(record, &mut streams.err)
+                self.try_log_term(record, &mut streams.err)?;
+                #[cfg(feature = "console-attention")]
+                if self.interactive {
+                    signal_error(&self.config, &mut streams.err)?;
+                }
+                Ok(())
             } else {
                 self.try_log_term(record, &mut streams.out)
             }
@@ -212,9 +467,171 @@ impl TermLogger {
     }
 }
 
+/// Escape sequence written by `Buffer::ansi` to reset the color
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Stream collecting a line and passing it to a hook on flush
+struct PrintHookStream {
+    buffer: Buffer,
+    hook: Arc<dyn Fn(&str) + Send + Sync>,
+}
+
+impl PrintHookStream {
+    fn new(color_choice: ColorChoice, hook: Arc<dyn Fn(&str) + Send + Sync>) -> PrintHookStream {
+        let buffer = match color_choice {
+            ColorChoice::Never => Buffer::no_color(),
+            _ => Buffer::ansi(),
+        };
+        PrintHookStream { buffer, hook }
+    }
+}
+
+impl Write for PrintHookStream {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        self.buffer.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if !self.buffer.is_empty() {
+            let line = String::from_utf8_lossy(self.buffer.as_slice());
+            // colored lines are reset after the line ending, keep the reset but drop the line ending
+            match line.strip_suffix(ANSI_RESET) {
+                Some(line) => (self.hook)(&format!(
+                    "{}{}",
+                    line.trim_end_matches(['\n', '\r']),
+                    ANSI_RESET
+                )),
+                None => (self.hook)(line.trim_end_matches(['\n', '\r'])),
+            }
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+}
+
+impl WriteColor for PrintHookStream {
+    fn supports_color(&self) -> bool {
+        self.buffer.supports_color()
+    }
+
+    fn set_color(&mut self, spec: &ColorSpec) -> Result<(), Error> {
+        self.buffer.set_color(spec)
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        self.buffer.reset()
+    }
+}
+
+/// Writes a part of the line using the given style,
+/// unless the whole line is already printed in the style of the level
+fn write_styled<W, F>(
+    term_lock: &mut W,
+    style: &Style,
+    colorize_line: bool,
+    write: F,
+) -> Result<(), Error>
+where
+    W: WriteColor + Sized,
+    F: FnOnce(&mut W) -> Result<(), Error>,
+{
+    if !colorize_line && *style != Style::new() {
+        term_lock.set_color(&style.to_color_spec())?;
+        write(term_lock)?;
+        return term_lock.reset();
+    }
+
+    write(term_lock)
+}
+
+/// Draws attention to an error record, see `ConfigBuilder::set_error_attention`
+#[cfg(feature = "console-attention")]
+pub(crate) fn signal_error<W>(config: &Config, term_lock: &mut W) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    let title = match &config.error_attention.title {
+        Some(title) => title,
+        None => return Ok(()),
+    };
+
+    let errors = config.count_error();
+    let plural = if errors == 1 { "" } else { "s" };
+    // OSC 0 sets the window title in most terminal emulators
+    write!(
+        term_lock,
+        "\x1b]0;{} ({} error{})\x07",
+        title, errors, plural
+    )?;
+    term_lock.flush()
+}
+
+/// Returns the color requested by the reserved `color` key-value of the record
+#[cfg(feature = "kv")]
+fn color_override(record: &Record<'_>) -> Option<termcolor::Color> {
+    let value = record.key_values().get(log::kv::Key::from_str("color"))?;
+    value.to_string().parse().ok()
+}
+
+#[cfg(not(feature = "kv"))]
+fn color_override(_record: &Record<'_>) -> Option<termcolor::Color> {
+    None
+}
+
+/// Writes the location wrapped into an OSC 8 hyperlink
+fn write_location_hyperlink<W>(
+    record: &Record<'_>,
+    write: &mut W,
+    config: &Config,
+    template: &str,
+    current_dir: Option<&Path>,
+) -> Result<(), Error>
+where
+    W: Write + Sized,
+{
+    let file = match record.file() {
+        Some(file) => Path::new(file),
+        None => return write_location(record, write, config),
+    };
+    let path = match current_dir {
+        Some(dir) if file.is_relative() => dir.join(file),
+        _ => file.to_path_buf(),
+    };
+    let line = record
+        .line()
+        .map(|line| line.to_string())
+        .unwrap_or_default();
+    let url = template
+        .replace("{file}", &percent_encode_path(&path))
+        .replace("{line}", &line);
+
+    write!(write, "\x1b]8;;{}\x1b\\", url)?;
+    write_location(record, write, config)?;
+    write!(write, "\x1b]8;;\x1b\\")
+}
+
+/// Percent-encodes the path for an url, keeping only unreserved characters, `/` and `:`
+///
+/// Spaces, `;` or escape sequences in the path would otherwise break the OSC 8 sequence.
+fn percent_encode_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => {
+                let _ = std::fmt::Write::write_fmt(&mut encoded, format_args!("%{:02X}", byte));
+            }
+        }
+    }
+    encoded
+}
+
 impl Log for TermLogger {
     fn enabled(&self, metadata: &Metadata<'_>) -> bool {
-        metadata.level() <= self.level
+        metadata.level() <= self.level && !should_skip_metadata(&self.config, metadata)
     }
 
     fn log(&self, record: &Record<'_>) {
@@ -240,4 +657,207 @@ impl SharedLogger for TermLogger {
     fn as_log(self: Box<Self>) -> Box<dyn Log> {
         Box::new(*self)
     }
+
+    fn name(&self) -> &'static str {
+        "TermLogger"
+    }
+
+    fn sink_description(&self) -> String {
+        String::from(self.sink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "kv", feature = "termcolor"))]
+    #[test]
+    fn color_override() {
+        use crate::{ColorChoice, ConfigBuilder, Style, TermLogger};
+        use log::{Level, LevelFilter, Log, Record};
+        use std::sync::{Arc, Mutex};
+
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let hook_lines = lines.clone();
+        let logger = TermLogger::with_print_hook(
+            LevelFilter::Info,
+            ConfigBuilder::new()
+                .set_time_level(LevelFilter::Off)
+                .set_level_style(Level::Info, Style::new())
+                .build(),
+            ColorChoice::Always,
+            Box::new(move |line| hook_lines.lock().unwrap().push(line.to_owned())),
+        );
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .args(format_args!("build finished"))
+                .key_values(&[("color", "green")])
+                .build(),
+        );
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .args(format_args!("plain"))
+                .build(),
+        );
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            [
+                "\x1b[0m\x1b[32m[INFO] build finished\x1b[0m",
+                "[INFO] plain",
+            ]
+        );
+    }
+
+    #[cfg(feature = "console-attention")]
+    #[test]
+    fn error_attention() {
+        use super::signal_error;
+        use crate::{Config, ConfigBuilder, ErrorAttention};
+
+        let mut out = Vec::new();
+        signal_error(&Config::default(), &mut out).unwrap();
+        assert!(out.is_empty());
+
+        let config = ConfigBuilder::new()
+            .set_error_attention(ErrorAttention::new().title("tool"))
+            .build();
+        signal_error(&config, &mut out).unwrap();
+        signal_error(&config, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b]0;tool (1 error)\x07\x1b]0;tool (2 errors)\x07"
+        );
+    }
+
+    #[test]
+    fn flush_level() {
+        use crate::{ConfigBuilder, SharedWriter, TermLogger};
+        use log::{Level, LevelFilter, Log, Record};
+        use std::io::{Result, Write};
+        use termcolor::NoColor;
+
+        /// Hands the written bytes to `flushed` only when flushed
+        struct Unflushed {
+            pending: Vec<u8>,
+            flushed: SharedWriter<Vec<u8>>,
+        }
+
+        impl Write for Unflushed {
+            fn write(&mut self, buf: &[u8]) -> Result<usize> {
+                self.pending.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> Result<()> {
+                self.flushed.write_all(&self.pending)?;
+                self.pending.clear();
+                Ok(())
+            }
+        }
+
+        let flushed = SharedWriter::new(Vec::new());
+        let stream = || {
+            Box::new(NoColor::new(Unflushed {
+                pending: Vec::new(),
+                flushed: flushed.clone(),
+            }))
+        };
+        let logger = TermLogger::with_streams(
+            LevelFilter::Trace,
+            ConfigBuilder::new()
+                .set_time_level(LevelFilter::Off)
+                .set_thread_level(LevelFilter::Off)
+                .set_target_level(LevelFilter::Off)
+                .set_location_level(LevelFilter::Off)
+                .set_flush_level(LevelFilter::Warn)
+                .build(),
+            stream(),
+            stream(),
+        );
+        let log = |level: Level| {
+            logger.log(
+                &Record::builder()
+                    .level(level)
+                    .args(format_args!("{}", level))
+                    .build(),
+            )
+        };
+
+        log(Level::Info);
+        log(Level::Debug);
+        assert!(flushed.lock().is_empty());
+        log(Level::Warn);
+        assert_eq!(
+            &*flushed.lock(),
+            b"[INFO] INFO\n[DEBUG] DEBUG\n[WARN] WARN\n"
+        );
+        log(Level::Trace);
+        logger.flush();
+        assert_eq!(
+            &*flushed.lock(),
+            b"[INFO] INFO\n[DEBUG] DEBUG\n[WARN] WARN\n[TRACE] TRACE\n"
+        );
+    }
+
+    #[test]
+    fn location_hyperlink() {
+        use super::write_location_hyperlink;
+        use crate::ConfigBuilder;
+        use log::{Level, Record};
+        use std::path::Path;
+
+        let config = ConfigBuilder::new()
+            .set_location_hyperlink(Some("file://{file}#{line}"))
+            .build();
+        let record = Record::builder()
+            .level(Level::Info)
+            .file(Some("src/my file;ü.rs"))
+            .line(Some(7))
+            .build();
+        let mut out = Vec::new();
+        write_location_hyperlink(
+            &record,
+            &mut out,
+            &config,
+            "file://{file}#{line}",
+            Some(Path::new("/home/me")),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b]8;;file:///home/me/src/my%20file%3B%C3%BC.rs#7\x1b\\[src/my file;ü.rs:7] \x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn no_hyperlinks_without_terminal() {
+        use crate::{ConfigBuilder, SharedWriter, TermLogger};
+        use log::{Level, LevelFilter, Log, Record};
+        use termcolor::Ansi;
+
+        let output = SharedWriter::new(Vec::new());
+        let logger = TermLogger::with_streams(
+            LevelFilter::Info,
+            ConfigBuilder::new()
+                .set_time_level(LevelFilter::Off)
+                .set_location_level(LevelFilter::Info)
+                .set_location_hyperlink(Some("file://{file}"))
+                .build(),
+            Box::new(Ansi::new(output.clone())),
+            Box::new(Ansi::new(output.clone())),
+        );
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .file(Some("src/main.rs"))
+                .line(Some(7))
+                .args(format_args!("started"))
+                .build(),
+        );
+        let output = String::from_utf8(output.lock().clone()).unwrap();
+        assert!(output.contains("src/main.rs:7"));
+        assert!(!output.contains("\x1b]8;;"));
+    }
 }
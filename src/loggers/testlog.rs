@@ -7,16 +7,64 @@
 
 //! Module providing the TestLogger Implementation
 
-use super::logging::should_skip;
-use crate::{config::TimeFormat, Config, LevelPadding, SharedLogger};
-use log::{set_boxed_logger, set_max_level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use super::install::{set_global_logger, tolerate_existing_logger};
+use super::logging::{should_skip_metadata, try_log};
+use crate::{Config, InitError, SharedLogger};
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::io::{stderr, stdout, Write};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 
-use std::thread;
+/// Handle to the records logged by a capturing TestLogger, see [`TestLogger::new_captured`]
+/// and [`TestLogger::init_captured`]
+#[derive(Clone, Default)]
+pub struct CapturedRecords {
+    records: Arc<Mutex<Vec<String>>>,
+}
+
+impl CapturedRecords {
+    /// Returns the handle to the process-wide mirror filled by the TestLogger installed with `init_captured`
+    ///
+    /// Any test can inspect the records logged through the global logger this way,
+    /// no matter which test installed it or which thread logged them.
+    /// As the tests of a binary run in parallel, it contains the records of all of them,
+    /// so look for the records of your test instead of comparing all of them.
+    pub fn global() -> CapturedRecords {
+        static GLOBAL: OnceLock<CapturedRecords> = OnceLock::new();
+        GLOBAL.get_or_init(CapturedRecords::default).clone()
+    }
+
+    /// Returns the records logged so far, without their line endings
+    pub fn records(&self) -> Vec<String> {
+        self.records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Clears the records returned by `records`
+    pub fn clear(&self) {
+        self.records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    fn push(&self, record: &str) {
+        self.records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(record.to_owned());
+    }
+}
 
 /// The TestLogger struct. Provides a very basic Logger implementation that may be captured by cargo.
 pub struct TestLogger {
     level: LevelFilter,
     config: Config,
+    stderr_level: LevelFilter,
+    captured: Option<CapturedRecords>,
+    /// Writes through the locked stdout/stderr handles instead of `print!`/`eprint!`
+    locked_output: bool,
 }
 
 impl TestLogger {
@@ -38,8 +86,25 @@ impl TestLogger {
     /// # }
     /// ```
     pub fn init(log_level: LevelFilter, config: Config) -> Result<(), SetLoggerError> {
-        set_max_level(log_level);
-        set_boxed_logger(TestLogger::new(log_level, config))
+        set_global_logger(TestLogger::new(log_level, config))
+    }
+
+    /// Like `init`, but tolerates an already initialized Logger.
+    ///
+    /// Returns `Ok(true)`, if the TestLogger was set as the global log facility,
+    /// and `Ok(false)`, if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// // every test may call this, only the first call installs the logger
+    /// TestLogger::try_init(LevelFilter::Info, Config::default()).unwrap();
+    /// # }
+    /// ```
+    pub fn try_init(log_level: LevelFilter, config: Config) -> Result<bool, InitError> {
+        tolerate_existing_logger(TestLogger::init(log_level, config))
     }
 
     /// allows to create a new logger, that can be independently used, no matter what is globally set.
@@ -63,25 +128,171 @@ impl TestLogger {
     /// ```
     #[must_use]
     pub fn new(log_level: LevelFilter, config: Config) -> Box<TestLogger> {
+        TestLogger::new_with_stderr_level(log_level, config, LevelFilter::Off)
+    }
+
+    /// Like `init`, but prints records up to `stderr_level` to stderr and all others to stdout.
+    ///
+    /// Fails if another Logger was already initialized.
+    pub fn init_with_stderr_level(
+        log_level: LevelFilter,
+        config: Config,
+        stderr_level: LevelFilter,
+    ) -> Result<(), SetLoggerError> {
+        set_global_logger(TestLogger::new_with_stderr_level(
+            log_level,
+            config,
+            stderr_level,
+        ))
+    }
+
+    /// Like `new`, but prints records up to `stderr_level` to stderr (using `eprint!`)
+    /// and all others to stdout, just like a `TermLogger` in `TerminalMode::Mixed` does
+    /// for `LevelFilter::Warn`.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// // errors and warnings are printed to stderr
+    /// let test_logger = TestLogger::new_with_stderr_level(LevelFilter::Info, Config::default(), LevelFilter::Warn);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new_with_stderr_level(
+        log_level: LevelFilter,
+        config: Config,
+        stderr_level: LevelFilter,
+    ) -> Box<TestLogger> {
         Box::new(TestLogger {
             level: log_level,
             config,
+            stderr_level,
+            captured: None,
+            locked_output: false,
         })
     }
+
+    /// Like `init`, but additionally mirrors every record into the process-wide `CapturedRecords::global`.
+    ///
+    /// Returns the handle to inspect the captured records.
+    /// Fails if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let _ = TestLogger::init_captured(LevelFilter::Info, Config::default());
+    /// std::thread::spawn(|| log::info!("from a thread")).join().unwrap();
+    /// assert!(CapturedRecords::global()
+    ///     .records()
+    ///     .iter()
+    ///     .any(|line| line.ends_with("from a thread")));
+    /// # }
+    /// ```
+    pub fn init_captured(
+        log_level: LevelFilter,
+        config: Config,
+    ) -> Result<CapturedRecords, SetLoggerError> {
+        let mut logger = TestLogger::new(log_level, config);
+        logger.captured = Some(CapturedRecords::global());
+        set_global_logger(logger)?;
+        Ok(CapturedRecords::global())
+    }
+
+    /// Like `new`, but additionally mirrors every record into an in-memory buffer
+    /// of this logger.
+    ///
+    /// Records logged by any thread, including threads spawned by the test,
+    /// can be inspected through the handle returned by `captured` afterwards.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use log::Log;
+    /// # fn main() {
+    /// let logger = TestLogger::new_captured(LevelFilter::Info, Config::default());
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| logger.log(&log::Record::builder().args(format_args!("from a thread")).build()));
+    /// });
+    /// let captured = logger.captured().unwrap();
+    /// assert!(captured.records().iter().any(|line| line.ends_with("from a thread")));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new_captured(log_level: LevelFilter, config: Config) -> Box<TestLogger> {
+        let mut logger = TestLogger::new(log_level, config);
+        logger.captured = Some(CapturedRecords::default());
+        logger
+    }
+
+    /// Returns the handle to the captured records, if this is a capturing TestLogger
+    pub fn captured(&self) -> Option<CapturedRecords> {
+        self.captured.clone()
+    }
+
+    /// Writes every record with a single write to the locked `std::io::stdout()` or `std::io::stderr()`
+    /// handle instead of using `print!`/`eprint!`.
+    ///
+    /// The records of concurrently logging threads never interleave this way, but the test harness
+    /// only captures the output of the print macros, so the records are shown even for passing tests.
+    /// Use a capturing TestLogger to make assertions on them.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let test_logger = TestLogger::new_captured(LevelFilter::Info, Config::default()).with_locked_output();
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_locked_output(mut self: Box<Self>) -> Box<TestLogger> {
+        self.locked_output = true;
+        self
+    }
 }
 
 impl Log for TestLogger {
     fn enabled(&self, metadata: &Metadata<'_>) -> bool {
-        metadata.level() <= self.level
+        metadata.level() <= self.level && !should_skip_metadata(&self.config, metadata)
     }
 
     fn log(&self, record: &Record<'_>) {
-        if self.enabled(record.metadata()) {
-            let _ = log(&self.config, record);
+        // the filters are checked while logging the record
+        if record.level() <= self.level {
+            let mut buffer = Vec::new();
+            let _ = try_log(&self.config, record, &mut buffer);
+            if buffer.is_empty() {
+                return;
+            }
+
+            let line = String::from_utf8_lossy(&buffer);
+            match (self.locked_output, record.level() <= self.stderr_level) {
+                (true, true) => {
+                    let _ = stderr().lock().write_all(&buffer);
+                }
+                (true, false) => {
+                    let _ = stdout().lock().write_all(&buffer);
+                }
+                (false, true) => eprint!("{}", line),
+                (false, false) => print!("{}", line),
+            }
+            if let Some(captured) = &self.captured {
+                let record = line.strip_suffix(&*self.config.line_ending);
+                captured.push(record.unwrap_or(&line));
+            }
         }
     }
 
-    fn flush(&self) {}
+    fn flush(&self) {
+        if self.locked_output {
+            let _ = stdout().flush();
+        }
+    }
 }
 
 impl SharedLogger for TestLogger {
@@ -96,96 +307,100 @@ impl SharedLogger for TestLogger {
     fn as_log(self: Box<Self>) -> Box<dyn Log> {
         Box::new(*self)
     }
-}
 
-#[inline(always)]
-pub fn log(config: &Config, record: &Record<'_>) {
-    if should_skip(&config, &record) {
-        return;
+    fn name(&self) -> &'static str {
+        "TestLogger"
     }
 
-    if config.time <= record.level() && config.time != LevelFilter::Off {
-        write_time(config);
+    fn sink_description(&self) -> String {
+        match self.stderr_level {
+            LevelFilter::Off => String::from("stdout"),
+            level => format!("stdout, {} and more severe to stderr", level),
+        }
     }
+}
 
-    if config.level <= record.level() && config.level != LevelFilter::Off {
-        write_level(record, config);
-    }
+#[cfg(test)]
+mod tests {
 
-    if config.thread < record.level() && config.thread != LevelFilter::Off {
-        write_thread_id();
-    }
+    #[cfg(feature = "test")]
+    #[test]
+    fn test_logger_captured() {
+        use crate::{Config, TestLogger};
+        use log::{Level, LevelFilter, Log, Record};
+        use std::thread;
 
-    if config.target <= record.level() && config.target != LevelFilter::Off {
-        write_target(record);
-    }
+        let logger = TestLogger::new_captured(LevelFilter::Info, Config::default());
+        thread::scope(|s| {
+            for level in [Level::Warn, Level::Debug] {
+                let logger = &logger;
+                s.spawn(move || {
+                    logger.log(
+                        &Record::builder()
+                            .level(level)
+                            .args(format_args!("captured {}", level))
+                            .build(),
+                    )
+                });
+            }
+        });
 
-    if config.location <= record.level() && config.location != LevelFilter::Off {
-        write_location(record);
-    }
+        let captured = logger.captured().unwrap().records();
+        assert!(captured
+            .iter()
+            .any(|line| line.ends_with("[WARN] captured WARN")));
+        assert!(!captured.iter().any(|line| line.contains("captured DEBUG")));
 
-    if config.module <= record.level() && config.module != LevelFilter::Off {
-        write_module(record);
+        // every logger captures its own records
+        let other = TestLogger::new_captured(LevelFilter::Info, Config::default());
+        assert!(other.captured().unwrap().records().is_empty());
+        assert!(TestLogger::new(LevelFilter::Info, Config::default())
+            .captured()
+            .is_none());
     }
 
-    write_args(record);
-}
-
-#[inline(always)]
-pub fn write_time(config: &Config) {
-    use time::format_description::well_known::*;
-
-    let time = time::OffsetDateTime::now_utc().to_offset(config.time_offset);
-    let res = match config.time_format {
-        TimeFormat::Rfc2822 => time.format(&Rfc2822),
-        TimeFormat::Rfc3339 => time.format(&Rfc3339),
-        TimeFormat::Custom(format) => time.format(&format),
-    };
-    match res {
-        Ok(time) => print!("{} ", time),
-        Err(err) => panic!("Invalid time format: {}", err),
-    };
-}
-
-#[inline(always)]
-pub fn write_level(record: &Record<'_>, config: &Config) {
-    match config.level_padding {
-        LevelPadding::Left => print!("[{: >5}] ", record.level()),
-        LevelPadding::Right => print!("[{: <5}] ", record.level()),
-        LevelPadding::Off => print!("[{}] ", record.level()),
-    };
-}
-
-#[inline(always)]
-pub fn write_thread_id() {
-    let id = format!("{:?}", thread::current().id());
-    let id = id.replace("ThreadId(", "");
-    let id = id.replace(")", "");
-    print!("({}) ", id);
-}
-
-#[inline(always)]
-pub fn write_target(record: &Record<'_>) {
-    print!("{}: ", record.target());
-}
+    #[cfg(feature = "test")]
+    #[test]
+    fn test_logger_sanitizes() {
+        use crate::{ConfigBuilder, TestLogger};
+        use log::{Level, LevelFilter, Log, Record};
 
-#[inline(always)]
-pub fn write_location(record: &Record<'_>) {
-    let file = record.file().unwrap_or("<unknown>");
-    if let Some(line) = record.line() {
-        print!("[{}:{}] ", file, line);
-    } else {
-        print!("[{}:<unknown>] ", file);
+        let logger = TestLogger::new_captured(
+            LevelFilter::Info,
+            ConfigBuilder::new()
+                .set_time_level(LevelFilter::Off)
+                .set_strip_ansi(true)
+                .set_escape_control_chars(true)
+                .build(),
+        );
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .args(format_args!("\x1b[31mred\x1b[0m\x07"))
+                .build(),
+        );
+        assert_eq!(logger.captured().unwrap().records(), ["[INFO] red\\x07"]);
     }
-}
 
-#[inline(always)]
-pub fn write_module(record: &Record<'_>) {
-    let module = record.module_path().unwrap_or("<unknown>");
-    print!("[{}] ", module);
-}
+    #[cfg(feature = "test")]
+    #[test]
+    fn test_logger_locked_output() {
+        use crate::{ConfigBuilder, TestLogger};
+        use log::{Level, LevelFilter, Log, Record};
 
-#[inline(always)]
-pub fn write_args(record: &Record<'_>) {
-    println!("{}", record.args());
+        let logger = TestLogger::new_captured(
+            LevelFilter::Info,
+            ConfigBuilder::new()
+                .set_time_level(LevelFilter::Off)
+                .build(),
+        )
+        .with_locked_output();
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .args(format_args!("locked"))
+                .build(),
+        );
+        assert_eq!(logger.captured().unwrap().records(), ["[INFO] locked"]);
+    }
 }
@@ -0,0 +1,150 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the TracingLayer Implementation
+
+use crate::SharedLogger;
+use log::{Level, MetadataBuilder, Record};
+use std::fmt::{self, Write};
+use tracing_core::field::{Field, Visit};
+use tracing_core::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// The TracingLayer struct. Renders `tracing` events through a simplelog logger.
+///
+/// Every event is converted into a `log` record and passed to the wrapped logger,
+/// so its `Config` and sinks are used for the output. The `message` of the event
+/// is followed by all other fields as `key=value` pairs. Spans are not recorded.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// # fn main() {
+/// let layer = TracingLayer::new(SimpleLogger::new(LevelFilter::Info, Config::default()));
+/// let subscriber = tracing_subscriber::registry().with(layer);
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!(user = "alice", "logged in");
+/// });
+/// # }
+/// ```
+pub struct TracingLayer {
+    logger: Box<dyn SharedLogger>,
+}
+
+impl TracingLayer {
+    /// Creates a new layer rendering events through the given logger
+    pub fn new(logger: Box<dyn SharedLogger>) -> TracingLayer {
+        TracingLayer { logger }
+    }
+
+    /// Returns the logger the events are rendered through
+    pub fn logger(&self) -> &dyn SharedLogger {
+        &*self.logger
+    }
+}
+
+impl<S: Subscriber> Layer<S> for TracingLayer {
+    fn enabled(&self, metadata: &tracing_core::Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        self.logger.enabled(
+            &MetadataBuilder::new()
+                .level(level(metadata.level()))
+                .target(metadata.target())
+                .build(),
+        )
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        self.logger.log(
+            &Record::builder()
+                .args(format_args!("{}{}", visitor.message, visitor.fields))
+                .level(level(metadata.level()))
+                .target(metadata.target())
+                .module_path(metadata.module_path())
+                .file(metadata.file())
+                .line(metadata.line())
+                .build(),
+        );
+    }
+}
+
+fn level(level: &tracing_core::Level) -> Level {
+    match *level {
+        tracing_core::Level::ERROR => Level::Error,
+        tracing_core::Level::WARN => Level::Warn,
+        tracing_core::Level::INFO => Level::Info,
+        tracing_core::Level::DEBUG => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+/// Collects the message and the other fields of an event
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ConfigBuilder;
+    use log::LevelFilter;
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_layer() {
+        use crate::{TracingLayer, WriteLogger};
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let config = ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_target_level(LevelFilter::Error)
+            .build();
+        let layer = TracingLayer::new(WriteLogger::new(LevelFilter::Info, config, Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let dispatch = tracing::Dispatch::new(subscriber);
+        tracing::dispatcher::with_default(&dispatch, || {
+            tracing::info!(target: "app", user = "alice", id = 7, "logged in");
+            tracing::debug!(target: "app", "not enabled");
+        });
+
+        let logger = dispatch
+            .downcast_ref::<TracingLayer>()
+            .unwrap()
+            .logger()
+            .as_any()
+            .downcast_ref::<WriteLogger<Vec<u8>>>()
+            .unwrap();
+        let out = logger.with_writer(|w| String::from_utf8(w.clone()).unwrap());
+        assert_eq!(out, "[INFO] app: logged in user=alice id=7\n");
+    }
+}
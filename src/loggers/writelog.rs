@@ -7,17 +7,27 @@
 
 //! Module providing the FileLogger Implementation
 
-use super::logging::try_log;
-use crate::{Config, SharedLogger};
-use log::{set_boxed_logger, set_max_level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use super::install::{set_global_logger, tolerate_existing_logger};
+use super::logging::{should_skip_metadata, try_log, with_render_buffer};
+#[cfg(any(unix, windows))]
+use crate::SocketWriter;
+use crate::{Config, InitError, SharedLogger, SharedWriter};
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
 use std::io::Write;
-use std::sync::Mutex;
+#[cfg(any(unix, windows))]
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 /// The WriteLogger struct. Provides a Logger implementation for structs implementing `Write`, e.g. File
+///
+/// Every record is rendered into a buffer first and handed to the writer with a single `write_all` call.
+/// Combined with a file opened in append mode (or a `LockedFile`) this keeps lines from different
+/// processes sharing the same file from interleaving.
 pub struct WriteLogger<W: Write + Send + 'static> {
     level: LevelFilter,
     config: Config,
     writable: Mutex<W>,
+    sink: String,
 }
 
 impl<W: Write + Send + 'static> WriteLogger<W> {
@@ -36,8 +46,33 @@ impl<W: Write + Send + 'static> WriteLogger<W> {
     /// # }
     /// ```
     pub fn init(log_level: LevelFilter, config: Config, writable: W) -> Result<(), SetLoggerError> {
-        set_max_level(log_level);
-        set_boxed_logger(WriteLogger::new(log_level, config, writable))
+        set_global_logger(WriteLogger::new(log_level, config, writable))
+    }
+
+    /// Like `init`, but tolerates an already initialized Logger.
+    ///
+    /// Returns `Ok(true)`, if the WriteLogger was set as the global log facility,
+    /// and `Ok(false)`, if another Logger was already initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::fs::File;
+    /// # fn main() {
+    /// match WriteLogger::try_init(LevelFilter::Info, Config::default(), File::create("my_rust_bin.log").unwrap()) {
+    ///     Ok(true) => {}
+    ///     Ok(false) => println!("logging was already set up"),
+    ///     Err(err) => println!("failed to set up logging: {}", err),
+    /// }
+    /// # }
+    /// ```
+    pub fn try_init(
+        log_level: LevelFilter,
+        config: Config,
+        writable: W,
+    ) -> Result<bool, InitError> {
+        tolerate_existing_logger(WriteLogger::init(log_level, config, writable))
     }
 
     /// allows to create a new logger, that can be independently used, no matter what is globally set.
@@ -62,19 +97,131 @@ impl<W: Write + Send + 'static> WriteLogger<W> {
             level: log_level,
             config,
             writable: Mutex::new(writable),
+            sink: String::from("writer"),
         })
     }
+
+    /// Sets the description returned by `SharedLogger::sink_description`, e.g. the path of the file written to
+    /// (default is `writer`)
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::fs::File;
+    /// # fn main() {
+    /// let file_logger = WriteLogger::new(LevelFilter::Info, Config::default(), File::create("my_rust_bin.log").unwrap())
+    ///     .with_sink_description("my_rust_bin.log");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_sink_description<S: Into<String>>(
+        mut self: Box<Self>,
+        description: S,
+    ) -> Box<WriteLogger<W>> {
+        self.sink = description.into();
+        self
+    }
+
+    /// Calls `f` with the underlying writer, e.g. to sync a file to disk at a checkpoint.
+    ///
+    /// Records logged by other threads wait until `f` returns.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use log::Log;
+    /// # fn main() {
+    /// let logger = WriteLogger::new(LevelFilter::Info, Config::default(), Vec::new());
+    /// logger.log(&log::Record::builder().level(Level::Info).args(format_args!("test")).build());
+    /// let output = logger.with_writer(|buffer| String::from_utf8(buffer.clone()).unwrap());
+    /// assert!(output.ends_with("test\n"));
+    /// # }
+    /// ```
+    pub fn with_writer<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut W) -> R,
+    {
+        f(&mut self.writable.lock().unwrap())
+    }
+
+    /// Consumes the logger, returning the underlying writer.
+    ///
+    /// The writer is not flushed.
+    pub fn into_inner(self) -> W {
+        self.writable.into_inner().unwrap()
+    }
+}
+
+impl<W: Write + Send + 'static> WriteLogger<SharedWriter<W>> {
+    /// Like `new`, but writes into a writer shared with the application.
+    ///
+    /// The application can lock the writer at any time, e.g. to write into it as well
+    /// or to read an in-memory buffer. See [`SharedWriter`] for an example.
+    ///
+    /// # Examples
+    /// ```
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # use std::sync::{Arc, Mutex};
+    /// # fn main() {
+    /// let buffer = Arc::new(Mutex::new(Vec::new()));
+    /// let logger = WriteLogger::new_shared(LevelFilter::Info, Config::default(), buffer.clone());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new_shared(
+        log_level: LevelFilter,
+        config: Config,
+        writable: Arc<Mutex<W>>,
+    ) -> Box<WriteLogger<SharedWriter<W>>> {
+        WriteLogger::new(log_level, config, SharedWriter::from(writable))
+    }
+}
+
+#[cfg(any(unix, windows))]
+impl WriteLogger<SocketWriter> {
+    /// Like `new`, but writes into the unix domain socket (or named pipe on Windows) at `path`.
+    ///
+    /// The socket is connected on the first record and reconnected whenever the connection breaks.
+    /// See [`SocketWriter`] for details.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let socket_logger = WriteLogger::new_socket(LevelFilter::Info, Config::default(), "/run/collector.sock");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new_socket<P: AsRef<Path>>(
+        log_level: LevelFilter,
+        config: Config,
+        path: P,
+    ) -> Box<WriteLogger<SocketWriter>> {
+        let path = path.as_ref();
+        WriteLogger::new(log_level, config, SocketWriter::new(path))
+            .with_sink_description(format!("socket {}", path.display()))
+    }
 }
 
 impl<W: Write + Send + 'static> Log for WriteLogger<W> {
     fn enabled(&self, metadata: &Metadata<'_>) -> bool {
-        metadata.level() <= self.level
+        metadata.level() <= self.level && !should_skip_metadata(&self.config, metadata)
     }
 
     fn log(&self, record: &Record<'_>) {
-        if self.enabled(record.metadata()) {
-            let mut write_lock = self.writable.lock().unwrap();
-            let _ = try_log(&self.config, record, &mut *write_lock);
+        // the filters are checked while logging the record
+        if record.level() <= self.level {
+            with_render_buffer(|buffer| {
+                let _ = try_log(&self.config, record, buffer);
+                if !buffer.is_empty() {
+                    let mut write_lock = self.writable.lock().unwrap();
+                    let _ = write_lock.write_all(buffer);
+                }
+            });
         }
     }
 
@@ -95,4 +242,12 @@ impl<W: Write + Send + 'static> SharedLogger for WriteLogger<W> {
     fn as_log(self: Box<Self>) -> Box<dyn Log> {
         Box::new(*self)
     }
+
+    fn name(&self) -> &'static str {
+        "WriteLogger"
+    }
+
+    fn sink_description(&self) -> String {
+        self.sink.clone()
+    }
 }
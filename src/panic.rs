@@ -0,0 +1,68 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing a panic hook logging through the active logger
+
+use log::{logger, Level, Record};
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::panic;
+use std::thread;
+
+/// Registers a panic hook, that logs panics through the global logger.
+///
+/// The panic message is logged at `Level::Error` with the target `panic`
+/// and the source location of the panic, afterwards the logger is flushed.
+/// If backtraces are enabled (e.g. through `RUST_BACKTRACE=1`), the backtrace is
+/// appended to the message.
+///
+/// This replaces any previously registered panic hook, including the default one,
+/// which prints the panic to stderr.
+///
+/// # Examples
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # fn main() {
+/// let _ = SimpleLogger::init(LevelFilter::Info, Config::default());
+/// install_panic_hook();
+/// # }
+/// ```
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        let payload = info.payload();
+        let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+            msg
+        } else if let Some(msg) = payload.downcast_ref::<String>() {
+            msg.as_str()
+        } else {
+            "Box<dyn Any>"
+        };
+        let thread = thread::current();
+        let name = thread.name().unwrap_or("<unnamed>");
+
+        let backtrace = Backtrace::capture();
+        let backtrace = match backtrace.status() {
+            BacktraceStatus::Captured => format!("\n{}", backtrace),
+            _ => String::new(),
+        };
+
+        let location = info.location();
+        logger().log(
+            &Record::builder()
+                .args(format_args!(
+                    "thread '{}' panicked at '{}'{}",
+                    name, msg, backtrace
+                ))
+                .level(Level::Error)
+                .target("panic")
+                .file(location.map(|l| l.file()))
+                .line(location.map(|l| l.line()))
+                .build(),
+        );
+        logger().flush();
+    }));
+}
@@ -0,0 +1,190 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing owned snapshots of log records
+
+use crate::loggers::logging::{with_snapshot, with_thread_info};
+use log::{Level, Log, Record};
+use std::time::SystemTime;
+
+/// An owned snapshot of a `log::Record`, including the time and thread it was logged at.
+///
+/// Snapshots can be buffered, reordered or sent to other threads or processes and
+/// rendered later on with `Config::render` or replayed into any logger with `replay`,
+/// producing the same output as if the record was logged directly.
+///
+/// # Examples
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # fn main() {
+/// let record = LogRecordOwned::capture(
+///     &log::Record::builder()
+///         .level(Level::Warn)
+///         .target("my_app")
+///         .args(format_args!("disk almost full"))
+///         .build(),
+/// );
+///
+/// let config = ConfigBuilder::new().set_time_level(LevelFilter::Off).build();
+/// let mut output = Vec::new();
+/// config.render(&record, &mut output).unwrap();
+/// assert_eq!(String::from_utf8(output).unwrap(), "[WARN] disk almost full\n");
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecordOwned {
+    /// Level of the record
+    pub level: Level,
+    /// Target of the record
+    pub target: String,
+    /// Time the record was logged at
+    pub time: SystemTime,
+    /// Id of the thread, that logged the record, as printed by the loggers
+    pub thread_id: String,
+    /// Name of the thread, that logged the record, if it has one
+    pub thread_name: Option<String>,
+    /// Module path of the source code, that logged the record
+    pub module_path: Option<String>,
+    /// File of the source code, that logged the record
+    pub file: Option<String>,
+    /// Line of the source code, that logged the record
+    pub line: Option<u32>,
+    /// The formatted message
+    pub message: String,
+    /// Key-values of the record (only captured with the `kv` feature)
+    pub key_values: Vec<(String, String)>,
+}
+
+impl LogRecordOwned {
+    /// Captures a snapshot of `record`, taking the current time and thread
+    pub fn capture(record: &Record<'_>) -> LogRecordOwned {
+        let (thread_id, thread_name) =
+            with_thread_info(|id, name| (id.to_string(), name.map(String::from)));
+        LogRecordOwned {
+            level: record.level(),
+            target: record.target().to_string(),
+            time: SystemTime::now(),
+            thread_id,
+            thread_name,
+            module_path: record.module_path().map(String::from),
+            file: record.file().map(String::from),
+            line: record.line(),
+            message: record.args().to_string(),
+            key_values: key_values(record),
+        }
+    }
+
+    /// Logs the snapshot through `logger`
+    ///
+    /// The loggers of this crate print the time and thread of the snapshot instead of the current ones.
+    pub fn replay(&self, logger: &dyn Log) {
+        self.with_record(|record| logger.log(record));
+    }
+
+    /// Calls `f` with the snapshot as a `log::Record`, while its time and thread are rendered
+    pub(crate) fn with_record<R>(&self, f: impl FnOnce(&Record<'_>) -> R) -> R {
+        with_snapshot(
+            self.time,
+            &self.thread_id,
+            self.thread_name.as_deref(),
+            || {
+                let mut builder = Record::builder();
+                builder
+                    .level(self.level)
+                    .target(&self.target)
+                    .module_path(self.module_path.as_deref())
+                    .file(self.file.as_deref())
+                    .line(self.line);
+                #[cfg(feature = "kv")]
+                builder.key_values(&self.key_values);
+                f(&builder.args(format_args!("{}", self.message)).build())
+            },
+        )
+    }
+}
+
+impl From<&Record<'_>> for LogRecordOwned {
+    fn from(record: &Record<'_>) -> LogRecordOwned {
+        LogRecordOwned::capture(record)
+    }
+}
+
+#[cfg(feature = "kv")]
+fn key_values(record: &Record<'_>) -> Vec<(String, String)> {
+    use log::kv::{Error, Key, Value, VisitSource};
+
+    struct Collect(Vec<(String, String)>);
+
+    impl<'kvs> VisitSource<'kvs> for Collect {
+        fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), Error> {
+            self.0.push((key.to_string(), value.to_string()));
+            Ok(())
+        }
+    }
+
+    let mut collect = Collect(Vec::new());
+    let _ = record.key_values().visit(&mut collect);
+    collect.0
+}
+
+#[cfg(not(feature = "kv"))]
+fn key_values(_record: &Record<'_>) -> Vec<(String, String)> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ConfigBuilder;
+    use log::{Level, LevelFilter, Record};
+    use std::time::Duration;
+
+    #[test]
+    fn owned_records() {
+        use crate::{LogRecordOwned, ThreadLogMode, WriteLogger};
+        use std::thread;
+        use std::time::SystemTime;
+
+        let mut record = thread::Builder::new()
+            .name("worker".into())
+            .spawn(|| {
+                LogRecordOwned::capture(
+                    &Record::builder()
+                        .level(Level::Warn)
+                        .target("my_app::db")
+                        .args(format_args!("slow query"))
+                        .build(),
+                )
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(record.thread_name.as_deref(), Some("worker"));
+        record.time = SystemTime::UNIX_EPOCH + Duration::from_secs(86400);
+
+        let config = ConfigBuilder::new()
+            .set_time_format_rfc3339()
+            .set_thread_level(LevelFilter::Warn)
+            .set_thread_mode(ThreadLogMode::Names)
+            .set_target_level(LevelFilter::Warn)
+            .build();
+        let mut rendered = Vec::new();
+        config.render(&record, &mut rendered).unwrap();
+        let rendered = String::from_utf8(rendered).unwrap();
+        assert_eq!(
+            rendered,
+            "1970-01-02T00:00:00Z [WARN] (worker) my_app::db: slow query\n"
+        );
+
+        let logger = WriteLogger::new(LevelFilter::Info, config, Vec::new());
+        record.replay(&*logger);
+        assert_eq!(
+            logger.with_writer(|w| String::from_utf8(w.clone()).unwrap()),
+            rendered
+        );
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing scopes indenting the records logged within them
+
+use log::{log, Level};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::time::Instant;
+
+/// Number of spaces records are indented by per scope
+const INDENT_WIDTH: usize = 2;
+
+thread_local! {
+    /// Number of scopes currently alive on this thread
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns the number of spaces the messages of the current thread are indented by
+pub(crate) fn indentation() -> usize {
+    DEPTH.with(|depth| depth.get()) * INDENT_WIDTH
+}
+
+/// Enters a scope logging at `Level::Info`, see [`Scope`].
+///
+/// # Examples
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # use log::info;
+/// # fn main() {
+/// let _ = SimpleLogger::init(LevelFilter::Info, Config::default());
+/// {
+///     let _scope = simplelog::scope("loading config");
+///     info!("reading config.toml");
+/// }
+/// # }
+/// ```
+/// prints
+/// ```text
+/// 00:00:00 [INFO] loading config
+/// 00:00:00 [INFO]   reading config.toml
+/// 00:00:00 [INFO] loading config done in 1.234ms
+/// ```
+pub fn scope<S: Into<Cow<'static, str>>>(name: S) -> Scope {
+    Scope::new(Level::Info, name)
+}
+
+/// Guard of a scope, giving the logs hierarchical structure without adopting `tracing`.
+///
+/// Entering the scope logs its name, dropping the guard logs the name again
+/// together with the time elapsed since. All messages logged by the same thread
+/// while the guard is alive (including nested scopes) are indented by two spaces.
+///
+/// Both records are logged with the target `scope`.
+#[must_use = "the scope is left immediately, if the guard is not kept alive"]
+pub struct Scope {
+    level: Level,
+    name: Cow<'static, str>,
+    start: Instant,
+    // the indentation is tracked per thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Scope {
+    /// Enters a scope logging at the given level
+    pub fn new<S: Into<Cow<'static, str>>>(level: Level, name: S) -> Scope {
+        let name = name.into();
+        log!(target: "scope", level, "{}", name);
+        DEPTH.with(|depth| depth.set(depth.get() + 1));
+        Scope {
+            level,
+            name,
+            start: Instant::now(),
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+        log!(
+            target: "scope",
+            self.level,
+            "{} done in {:.3?}",
+            self.name,
+            self.start.elapsed()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Config;
+    use log::{Level, LevelFilter, Record};
+
+    #[test]
+    fn scopes() {
+        use crate::{Scope, WriteLogger};
+        use log::Log;
+
+        let logger = WriteLogger::new(LevelFilter::Info, Config::minimal(), Vec::new());
+        let log = |msg: &str| {
+            logger.log(
+                &Record::builder()
+                    .level(Level::Info)
+                    .args(format_args!("{}", msg))
+                    .build(),
+            )
+        };
+        log("before");
+        {
+            let _outer = Scope::new(Level::Trace, "outer");
+            log("outer");
+            {
+                let _inner = Scope::new(Level::Trace, "inner");
+                log("inner\nline");
+            }
+            log("outer again");
+        }
+        log("after");
+        let out = logger.into_inner();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "before\n  outer\n    inner\nline\n  outer again\nafter\n"
+        );
+    }
+}
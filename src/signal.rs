@@ -0,0 +1,195 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing runtime level control through unix signals
+
+use crate::background::BackgroundThread;
+use crate::CombinedLevelHandle;
+use log::{max_level, set_max_level, LevelFilter};
+use signal_hook::iterator::Signals;
+use std::io;
+use std::os::raw::c_int;
+
+/// Handle to the background thread of [`install_signal_level_control`]
+///
+/// Dropping the handle keeps the thread running.
+pub struct SignalLevelControl {
+    thread: BackgroundThread,
+}
+
+impl SignalLevelControl {
+    /// Stops the background thread and waits for it to exit.
+    ///
+    /// The signals are ignored afterwards. `simplelog::shutdown` stops the thread as well.
+    pub fn stop(&self) {
+        self.thread.stop();
+    }
+}
+
+/// Raises the global maximum level by one step on receiving the signal `up`
+/// and lowers it by one step on receiving the signal `down`.
+///
+/// A background thread waits for the signals, so a running program can e.g. be switched
+/// to debug logging through `kill -USR1 <pid>` without restarting it.
+/// Every change is logged at `Level::Warn`, if still enabled.
+///
+/// Records are filtered by the levels of the loggers as well, so the maximum level can only
+/// be raised up to the most verbose level of the installed loggers. To be able to raise it up
+/// to `Trace`, create the loggers with `LevelFilter::Trace` and lower the maximum level
+/// afterwards through `log::set_max_level`, or use `install_signal_level_control_with_handle`.
+///
+/// Fails if the signal handlers cannot be registered, e.g. for forbidden signals like `SIGKILL`,
+/// or the thread could not be spawned.
+///
+/// # Examples
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// use signal_hook::consts::{SIGUSR1, SIGUSR2};
+///
+/// # fn main() {
+/// let _ = SimpleLogger::init(LevelFilter::Trace, Config::default());
+/// log::set_max_level(LevelFilter::Info);
+/// install_signal_level_control(SIGUSR1, SIGUSR2).unwrap();
+/// # }
+/// ```
+pub fn install_signal_level_control(up: c_int, down: c_int) -> io::Result<SignalLevelControl> {
+    install(up, down, None)
+}
+
+/// Like `install_signal_level_control`, but changes the levels of the loggers of a `CombinedLogger`.
+///
+/// Every logger is raised or lowered by one step, limited by the level it was created with,
+/// and the global maximum level follows the most verbose one. The CombinedLogger needs to be
+/// the installed logger (or wrapped by it).
+///
+/// # Examples
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// use signal_hook::consts::{SIGUSR1, SIGUSR2};
+///
+/// # fn main() {
+/// if let Ok(handle) = CombinedLogger::init_with_handle(vec![
+///     SimpleLogger::new(LevelFilter::Trace, Config::default()),
+/// ]) {
+///     handle.set_child_level(0, LevelFilter::Info);
+///     install_signal_level_control_with_handle(SIGUSR1, SIGUSR2, handle).unwrap();
+/// }
+/// # }
+/// ```
+pub fn install_signal_level_control_with_handle(
+    up: c_int,
+    down: c_int,
+    handle: CombinedLevelHandle,
+) -> io::Result<SignalLevelControl> {
+    install(up, down, Some(handle))
+}
+
+fn install(
+    up: c_int,
+    down: c_int,
+    handle: Option<CombinedLevelHandle>,
+) -> io::Result<SignalLevelControl> {
+    let mut signals = Signals::new([up, down])?;
+    let close = signals.handle();
+    let thread = BackgroundThread::spawn(
+        "simplelog-signals",
+        move || {
+            for signal in signals.forever() {
+                let step = if signal == up {
+                    more_verbose
+                } else {
+                    less_verbose
+                };
+                let level = change_level(handle.as_ref(), step);
+                log::warn!(target: "simplelog", "Changed the log level to {}", level);
+            }
+        },
+        move || close.close(),
+    )?;
+    Ok(SignalLevelControl { thread })
+}
+
+fn change_level(
+    handle: Option<&CombinedLevelHandle>,
+    step: fn(LevelFilter) -> LevelFilter,
+) -> LevelFilter {
+    let level = match handle {
+        Some(handle) => handle.step_levels(step),
+        None => step(max_level()),
+    };
+    set_max_level(level);
+    level
+}
+
+fn more_verbose(level: LevelFilter) -> LevelFilter {
+    match level {
+        LevelFilter::Off => LevelFilter::Error,
+        LevelFilter::Error => LevelFilter::Warn,
+        LevelFilter::Warn => LevelFilter::Info,
+        LevelFilter::Info => LevelFilter::Debug,
+        LevelFilter::Debug | LevelFilter::Trace => LevelFilter::Trace,
+    }
+}
+
+fn less_verbose(level: LevelFilter) -> LevelFilter {
+    match level {
+        LevelFilter::Off | LevelFilter::Error => LevelFilter::Off,
+        LevelFilter::Warn => LevelFilter::Error,
+        LevelFilter::Info => LevelFilter::Warn,
+        LevelFilter::Debug => LevelFilter::Info,
+        LevelFilter::Trace => LevelFilter::Debug,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{less_verbose, more_verbose};
+    use crate::{CombinedLogger, Config, SimpleLogger};
+    use log::LevelFilter;
+
+    #[test]
+    fn verbosity_steps() {
+        let levels = [
+            LevelFilter::Off,
+            LevelFilter::Error,
+            LevelFilter::Warn,
+            LevelFilter::Info,
+            LevelFilter::Debug,
+            LevelFilter::Trace,
+        ];
+        for pair in levels.windows(2) {
+            assert_eq!(more_verbose(pair[0]), pair[1]);
+            assert_eq!(less_verbose(pair[1]), pair[0]);
+        }
+        assert_eq!(more_verbose(LevelFilter::Trace), LevelFilter::Trace);
+        assert_eq!(less_verbose(LevelFilter::Off), LevelFilter::Off);
+    }
+
+    #[test]
+    fn step_handle_levels() {
+        let combined = CombinedLogger::new(vec![
+            SimpleLogger::new(LevelFilter::Debug, Config::default()),
+            SimpleLogger::new(LevelFilter::Info, Config::default()),
+        ]);
+        let handle = combined.level_handle();
+        handle.set_child_level(0, LevelFilter::Warn);
+
+        assert_eq!(handle.step_levels(more_verbose), LevelFilter::Info);
+        assert_eq!(handle.child_level(0), Some(LevelFilter::Info));
+        assert_eq!(handle.child_level(1), Some(LevelFilter::Info));
+
+        // limited by the levels the loggers were created with
+        assert_eq!(handle.step_levels(more_verbose), LevelFilter::Debug);
+        assert_eq!(handle.child_level(1), Some(LevelFilter::Info));
+
+        assert_eq!(handle.step_levels(less_verbose), LevelFilter::Info);
+        assert_eq!(handle.child_level(0), Some(LevelFilter::Info));
+        assert_eq!(handle.child_level(1), Some(LevelFilter::Warn));
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the EncryptedWriter writer
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+const NONCE_LEN: usize = 12;
+
+/// A writer encrypting everything written to it with AES-256-GCM.
+///
+/// `WriteLogger` emits each record with a single `write_all`, which is encrypted into one
+/// self-contained frame and written to the underlying writer right away. A crash can therefore
+/// at most lose the frame being written, all previous records stay decryptable.
+///
+/// Every frame consists of
+/// - the length of the rest of the frame as 32-bit big-endian integer,
+/// - a random 96-bit nonce,
+/// - the encrypted data followed by the 128-bit authentication tag.
+///
+/// Frames can be appended to an existing file and are decrypted using [`decrypt_log`],
+/// e.g. through `cargo run --example decrypt_log --features encryption -- <hex key> <file>`.
+///
+/// # Examples
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # use log::Log;
+/// # fn main() {
+/// let key = [7u8; 32];
+/// let logger = WriteLogger::new(LevelFilter::Info, Config::minimal(), EncryptedWriter::new(Vec::new(), &key));
+/// logger.log(&log::Record::builder().level(Level::Info).args(format_args!("secret")).build());
+///
+/// let encrypted = logger.into_inner().into_inner();
+/// let mut decrypted = Vec::new();
+/// decrypt_log(&encrypted[..], &key, &mut decrypted).unwrap();
+/// assert_eq!(decrypted, b"secret\n");
+/// # }
+/// ```
+pub struct EncryptedWriter<W: Write> {
+    writer: W,
+    cipher: Aes256Gcm,
+}
+
+impl<W: Write> EncryptedWriter<W> {
+    /// Wraps the writer, encrypting with the given 256-bit key
+    pub fn new(writer: W, key: &[u8; 32]) -> EncryptedWriter<W> {
+        EncryptedWriter {
+            writer,
+            cipher: Aes256Gcm::new(key.into()),
+        }
+    }
+
+    /// Returns a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Consumes the EncryptedWriter, returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Write for EncryptedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, buf)
+            .map_err(|_| Error::other("Failed to encrypt the record"))?;
+
+        let len = u32::try_from(NONCE_LEN + ciphertext.len())
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, "Record too large"))?;
+        let mut frame = Vec::with_capacity(4 + len as usize);
+        frame.extend_from_slice(&len.to_be_bytes());
+        frame.extend_from_slice(&nonce);
+        frame.extend_from_slice(&ciphertext);
+        self.writer.write_all(&frame)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Decrypts a log written through an [`EncryptedWriter`] using the same key, writing the plaintext into `output`.
+///
+/// Fails with `ErrorKind::InvalidData`, if a frame cannot be decrypted, e.g. because of a wrong key
+/// or a modified file, and with `ErrorKind::UnexpectedEof` for a truncated last frame.
+/// All frames before the failing one have been written into `output` in that case.
+pub fn decrypt_log<R: Read, W: Write>(mut input: R, key: &[u8; 32], mut output: W) -> Result<()> {
+    let cipher = Aes256Gcm::new(key.into());
+    loop {
+        let mut len = [0u8; 4];
+        match input.read(&mut len[..1])? {
+            0 => return output.flush(),
+            _ => input.read_exact(&mut len[1..])?,
+        }
+
+        let len = u32::from_be_bytes(len) as usize;
+        if len < NONCE_LEN {
+            return Err(Error::new(ErrorKind::InvalidData, "Invalid frame length"));
+        }
+        let mut frame = vec![0u8; len];
+        input.read_exact(&mut frame)?;
+
+        let (nonce, ciphertext) = frame.split_at(NONCE_LEN);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "Failed to decrypt the log"))?;
+        output.write_all(&plaintext)?;
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the HashChainWriter writer
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt::Write as _;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Error, ErrorKind, Result, Write};
+use std::path::Path;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const MARKER_PREFIX: &[u8] = b" [chain:";
+/// Length of the marker appended to every record: prefix, 64 hex digits and the closing bracket
+const MARKER_LEN: usize = MARKER_PREFIX.len() + 64 + 1;
+
+/// A writer making modifications of the log detectable, e.g. for compliance logs.
+///
+/// Every record is followed by the HMAC-SHA256 of the record and the HMAC of the previous record,
+/// chaining all records of the file together: `... your_message [chain:<64 hex digits>]`.
+/// Modifying, removing or reordering records breaks the chain, which is detected by [`verify_log`].
+///
+/// Without knowing the key the chain cannot be recomputed after modifying the file.
+/// An empty key results in a plain hash chain, which only detects modifications
+/// not recomputing the chain afterwards.
+///
+/// `WriteLogger` emits each record with a single `write_all`, which is required to chain whole records.
+/// The records need to end with `LineEnding::LF` or `LineEnding::Crlf`.
+///
+/// # Examples
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # use log::Log;
+/// # fn main() {
+/// let logger = WriteLogger::new(LevelFilter::Info, Config::minimal(), HashChainWriter::new(Vec::new(), b"secret key"));
+/// logger.log(&log::Record::builder().level(Level::Info).args(format_args!("audited")).build());
+///
+/// let log = String::from_utf8(logger.into_inner().into_inner()).unwrap();
+/// assert!(log.starts_with("audited [chain:"));
+/// # }
+/// ```
+pub struct HashChainWriter<W: Write> {
+    writer: W,
+    mac: HmacSha256,
+    previous: [u8; 32],
+}
+
+impl<W: Write> HashChainWriter<W> {
+    /// Wraps the writer, starting a new chain
+    pub fn new(writer: W, key: &[u8]) -> HashChainWriter<W> {
+        HashChainWriter::with_previous(writer, key, [0; 32])
+    }
+
+    fn with_previous(writer: W, key: &[u8], previous: [u8; 32]) -> HashChainWriter<W> {
+        HashChainWriter {
+            writer,
+            mac: HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length"),
+            previous,
+        }
+    }
+
+    /// Returns the hash of the last record written, the head of the chain.
+    ///
+    /// Store it outside of the log to detect truncation with [`verify_log_head`].
+    pub fn head(&self) -> [u8; 32] {
+        self.previous
+    }
+
+    /// Returns a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Consumes the HashChainWriter, returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl HashChainWriter<File> {
+    /// Opens (or creates) the file at `path` in append mode, continuing the chain of the records in it.
+    pub fn open<P: AsRef<Path>>(path: P, key: &[u8]) -> Result<HashChainWriter<File>> {
+        let path = path.as_ref();
+        let previous = match fs::read(path) {
+            Ok(contents) => {
+                let mut previous = [0; 32];
+                for record in records(&contents) {
+                    previous = record?.1;
+                }
+                previous
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => [0; 32],
+            Err(err) => return Err(err),
+        };
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(HashChainWriter::with_previous(file, key, previous))
+    }
+}
+
+impl<W: Write> Write for HashChainWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        let (content, ending): (&[u8], &[u8]) = if let Some(content) = buf.strip_suffix(b"\r\n") {
+            (content, b"\r\n")
+        } else if let Some(content) = buf.strip_suffix(b"\n") {
+            (content, b"\n")
+        } else {
+            (buf, b"\n")
+        };
+
+        let hash = chain(&self.mac, &self.previous, content);
+        let mut marker = String::with_capacity(MARKER_LEN);
+        marker.push_str(" [chain:");
+        for byte in &hash {
+            let _ = write!(marker, "{:02x}", byte);
+        }
+        marker.push(']');
+
+        let mut record = Vec::with_capacity(content.len() + MARKER_LEN + ending.len());
+        record.extend_from_slice(content);
+        record.extend_from_slice(marker.as_bytes());
+        record.extend_from_slice(ending);
+        self.writer.write_all(&record)?;
+        self.previous = hash;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Verifies the chain of a log written through a [`HashChainWriter`] using the same key.
+///
+/// Returns the number of verified records. Fails with `ErrorKind::InvalidData`,
+/// if the chain is broken, naming the line of the first record not matching the chain.
+///
+/// Removing records from the end of the log keeps the remaining chain intact and is not detected.
+/// Use [`verify_log_head`] with the [`HashChainWriter::head`] stored elsewhere to detect truncation.
+///
+/// # Examples
+/// ```no_run
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # fn main() {
+/// match verify_log("audit.log", b"secret key") {
+///     Ok(records) => println!("{} records verified", records),
+///     Err(err) => println!("verification failed: {}", err),
+/// }
+/// # }
+/// ```
+pub fn verify_log<P: AsRef<Path>>(path: P, key: &[u8]) -> Result<usize> {
+    verify_chain(path.as_ref(), key).map(|(count, _)| count)
+}
+
+/// Like [`verify_log`], but additionally checks that the chain ends with `head`,
+/// the [`HashChainWriter::head`] after writing the last record.
+///
+/// Fails with `ErrorKind::InvalidData`, if records were removed from or appended to the end of the log.
+pub fn verify_log_head<P: AsRef<Path>>(path: P, key: &[u8], head: &[u8; 32]) -> Result<usize> {
+    let (count, previous) = verify_chain(path.as_ref(), key)?;
+    if previous != *head {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Hash chain does not end with the expected head after {} records",
+                count
+            ),
+        ));
+    }
+    Ok(count)
+}
+
+/// Verifies the chain, returning the number of records and the hash of the last one
+fn verify_chain(path: &Path, key: &[u8]) -> Result<(usize, [u8; 32])> {
+    let contents = fs::read(path)?;
+    let mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    let mut previous = [0; 32];
+    let mut count = 0;
+    for record in records(&contents) {
+        let (content, hash, line) = record?;
+        if chain(&mac, &previous, content) != hash {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Hash chain broken at line {}", line),
+            ));
+        }
+        previous = hash;
+        count += 1;
+    }
+    Ok((count, previous))
+}
+
+/// Computes the hash of a record chained to the previous one
+fn chain(mac: &HmacSha256, previous: &[u8; 32], content: &[u8]) -> [u8; 32] {
+    let mut mac = mac.clone();
+    mac.update(previous);
+    mac.update(content);
+    mac.finalize().into_bytes().into()
+}
+
+/// Iterates over the content, hash and last line number of every record in the file
+fn records(contents: &[u8]) -> impl Iterator<Item = Result<(&[u8], [u8; 32], usize)>> {
+    let mut start = 0;
+    let mut position = 0;
+    let mut line = 0;
+    std::iter::from_fn(move || {
+        while position < contents.len() {
+            let line_start = position;
+            let end = contents[line_start..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(contents.len(), |i| line_start + i);
+            let text = &contents[line_start..end];
+            let text = text.strip_suffix(b"\r").unwrap_or(text);
+            line += 1;
+            position = end + 1;
+
+            if let Some(hash) = marker_hash(text) {
+                let marker_start = line_start + text.len() - MARKER_LEN;
+                let record = (&contents[start..marker_start], hash, line);
+                start = position;
+                return Some(Ok(record));
+            }
+        }
+        if start < contents.len() {
+            start = contents.len();
+            return Some(Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Unterminated record at line {}", line),
+            )));
+        }
+        None
+    })
+}
+
+/// Parses the hash of the marker at the end of the line, if there is one
+fn marker_hash(line: &[u8]) -> Option<[u8; 32]> {
+    let marker = line.get(line.len().checked_sub(MARKER_LEN)?..)?;
+    let hex = marker.strip_prefix(MARKER_PREFIX)?.strip_suffix(b"]")?;
+    let mut hash = [0; 32];
+    for (byte, digits) in hash.iter_mut().zip(hex.chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()?;
+    }
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Config;
+    use log::{Level, LevelFilter, Record};
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn hash_chain() {
+        use crate::{verify_log, HashChainWriter, WriteLogger};
+        use log::Log;
+
+        let path = std::env::temp_dir().join(format!("simplelog-audit-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let log = |msg: &str| {
+            let logger = WriteLogger::new(
+                LevelFilter::Info,
+                Config::minimal(),
+                HashChainWriter::open(&path, b"key").unwrap(),
+            );
+            logger.log(
+                &Record::builder()
+                    .level(Level::Info)
+                    .args(format_args!("{}", msg))
+                    .build(),
+            );
+        };
+        log("first");
+        log("multi\nline");
+        log("third");
+        assert_eq!(verify_log(&path, b"key").unwrap(), 3);
+        assert!(verify_log(&path, b"other key").is_err());
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, contents.replace("third", "forged")).unwrap();
+        let err = verify_log(&path, b"key").unwrap_err();
+        assert_eq!(err.to_string(), "Hash chain broken at line 4");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn hash_chain_truncation() {
+        use crate::{verify_log, verify_log_head, HashChainWriter, WriteLogger};
+        use log::Log;
+
+        let path = std::env::temp_dir().join(format!(
+            "simplelog-audit-truncation-{}.log",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let logger = WriteLogger::new(
+            LevelFilter::Info,
+            Config::minimal(),
+            HashChainWriter::open(&path, b"key").unwrap(),
+        );
+        for msg in &["first", "second"] {
+            logger.log(
+                &Record::builder()
+                    .level(Level::Info)
+                    .args(format_args!("{}", msg))
+                    .build(),
+            );
+        }
+        let head = logger.into_inner().head();
+        assert_eq!(verify_log_head(&path, b"key", &head).unwrap(), 2);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, contents.lines().next().unwrap().to_string() + "\n").unwrap();
+        assert_eq!(verify_log(&path, b"key").unwrap(), 1);
+        let err = verify_log_head(&path, b"key", &head).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Hash chain does not end with the expected head after 1 records"
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the LockedFile writer
+
+use fs2::FileExt;
+use std::fs::{File, OpenOptions};
+use std::io::{Result, Write};
+use std::path::Path;
+
+/// A log file that can safely be shared between multiple processes.
+///
+/// Every call to `write_all` takes an exclusive advisory lock
+/// (`flock` on unix, `LockFileEx` on windows) on the file for the duration of the write.
+/// `WriteLogger` emits each record with a single `write_all`, so records written by
+/// different processes never interleave, as long as every process uses a `LockedFile`.
+///
+/// Files opened through [`LockedFile::open`] are additionally opened in append mode,
+/// so every record is placed at the current end of the file.
+pub struct LockedFile {
+    file: File,
+}
+
+impl LockedFile {
+    /// Opens (or creates) the file at `path` in append mode.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # extern crate simplelog;
+    /// # use simplelog::*;
+    /// # fn main() {
+    /// let _ = WriteLogger::init(LevelFilter::Info, Config::default(), LockedFile::open("shared.log").unwrap());
+    /// # }
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<LockedFile> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(LockedFile { file })
+    }
+
+    /// Returns a reference to the underlying file
+    pub fn get_ref(&self) -> &File {
+        &self.file
+    }
+}
+
+impl From<File> for LockedFile {
+    fn from(file: File) -> LockedFile {
+        LockedFile { file }
+    }
+}
+
+impl Write for LockedFile {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.file.lock_exclusive()?;
+        let res = self.file.write(buf);
+        FileExt::unlock(&self.file)?;
+        res
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.file.lock_exclusive()?;
+        let res = self.file.write_all(buf);
+        FileExt::unlock(&self.file)?;
+        res
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LockedFile;
+    use crate::{Config, WriteLogger};
+    use log::{Level, LevelFilter, Log, Record};
+    use std::fs;
+
+    #[test]
+    fn locked_file_appends() {
+        let path =
+            std::env::temp_dir().join(format!("simplelog_locked_{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        // two loggers sharing the file, like two processes would
+        let first = WriteLogger::new(
+            LevelFilter::Info,
+            Config::minimal(),
+            LockedFile::open(&path).unwrap(),
+        );
+        let second = WriteLogger::new(
+            LevelFilter::Info,
+            Config::minimal(),
+            LockedFile::open(&path).unwrap(),
+        );
+        for (logger, msg) in [(&first, "first"), (&second, "second"), (&first, "third")] {
+            logger.log(
+                &Record::builder()
+                    .level(Level::Info)
+                    .args(format_args!("{}", msg))
+                    .build(),
+            );
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\nsecond\nthird\n");
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,19 @@
+#[cfg(feature = "encryption")]
+mod encryptedwriter;
+#[cfg(feature = "audit")]
+mod hashchainwriter;
+#[cfg(feature = "file-lock")]
+mod lockedfile;
+mod sharedwriter;
+#[cfg(any(unix, windows))]
+mod socketwriter;
+
+#[cfg(feature = "encryption")]
+pub use self::encryptedwriter::{decrypt_log, EncryptedWriter};
+#[cfg(feature = "audit")]
+pub use self::hashchainwriter::{verify_log, verify_log_head, HashChainWriter};
+#[cfg(feature = "file-lock")]
+pub use self::lockedfile::LockedFile;
+pub use self::sharedwriter::SharedWriter;
+#[cfg(any(unix, windows))]
+pub use self::socketwriter::SocketWriter;
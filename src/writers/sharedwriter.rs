@@ -0,0 +1,83 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the SharedWriter writer
+
+use std::io::{Result, Write};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A writer that can be shared between a logger and the application.
+///
+/// Clones write into the same underlying writer, so the application can keep a clone
+/// to write into the sink as well (e.g. section separators) or to read an in-memory buffer,
+/// while a `WriteLogger` owns another one. Every record is written while holding the lock,
+/// so writes of the application never end up in the middle of a record.
+///
+/// # Examples
+/// ```
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # use log::Log;
+/// # use std::io::Write;
+/// # fn main() {
+/// let buffer = SharedWriter::new(Vec::new());
+/// let logger = WriteLogger::new(LevelFilter::Info, Config::minimal(), buffer.clone());
+/// logger.log(&log::Record::builder().level(Level::Info).args(format_args!("test")).build());
+/// writeln!(buffer.clone(), "----").unwrap();
+/// assert_eq!(&*buffer.lock(), b"test\n----\n");
+/// # }
+/// ```
+pub struct SharedWriter<W> {
+    inner: Arc<Mutex<W>>,
+}
+
+impl<W> SharedWriter<W> {
+    /// Wraps the given writer to share it
+    pub fn new(writer: W) -> SharedWriter<W> {
+        SharedWriter {
+            inner: Arc::new(Mutex::new(writer)),
+        }
+    }
+
+    /// Locks the underlying writer, blocking records from being written until the guard is dropped
+    pub fn lock(&self) -> MutexGuard<'_, W> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the shared underlying writer
+    pub fn get_ref(&self) -> &Arc<Mutex<W>> {
+        &self.inner
+    }
+}
+
+impl<W> Clone for SharedWriter<W> {
+    fn clone(&self) -> SharedWriter<W> {
+        SharedWriter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<W> From<Arc<Mutex<W>>> for SharedWriter<W> {
+    fn from(inner: Arc<Mutex<W>>) -> SharedWriter<W> {
+        SharedWriter { inner }
+    }
+}
+
+impl<W: Write> Write for SharedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.lock().write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.lock().write_all(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.lock().flush()
+    }
+}
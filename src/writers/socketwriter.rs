@@ -0,0 +1,159 @@
+// Copyright 2016 Victor Brekenfeld
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Module providing the SocketWriter writer
+
+#[cfg(windows)]
+use std::fs::{File, OpenOptions};
+use std::io::{Result, Write};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)]
+type Stream = UnixStream;
+#[cfg(windows)]
+type Stream = File;
+
+/// A writer sending records to a unix domain socket (or a named pipe on Windows), e.g. of a log collector.
+///
+/// A plain `UnixStream` fails permanently once the other side goes away. The SocketWriter instead
+/// drops a broken connection and reconnects to the path, retrying the failed record once.
+/// If the socket cannot be reached, the record is dropped and the next record tries to connect again.
+///
+/// On Windows the path is the name of a named pipe, e.g. `\\.\pipe\collector`.
+///
+/// # Examples
+/// ```no_run
+/// # extern crate simplelog;
+/// # use simplelog::*;
+/// # fn main() {
+/// let _ = WriteLogger::init(LevelFilter::Info, Config::default(), SocketWriter::new("/run/collector.sock"));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SocketWriter {
+    path: PathBuf,
+    stream: Option<Stream>,
+}
+
+impl SocketWriter {
+    /// Creates a writer for the socket at `path`, connecting on the first record
+    pub fn new<P: AsRef<Path>>(path: P) -> SocketWriter {
+        SocketWriter {
+            path: path.as_ref().to_path_buf(),
+            stream: None,
+        }
+    }
+
+    /// Like `new`, but connects right away, failing if the socket cannot be reached
+    pub fn connect<P: AsRef<Path>>(path: P) -> Result<SocketWriter> {
+        let mut writer = SocketWriter::new(path);
+        writer.stream()?;
+        Ok(writer)
+    }
+
+    /// Returns the path of the socket
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true`, if the writer is currently connected
+    pub fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    fn stream(&mut self) -> Result<&mut Stream> {
+        if self.stream.is_none() {
+            self.stream = Some(open(&self.path)?);
+        }
+        Ok(self.stream.as_mut().unwrap())
+    }
+
+    fn try_write_all(&mut self, buf: &[u8]) -> Result<()> {
+        let result = self.stream().and_then(|stream| stream.write_all(buf));
+        if result.is_err() {
+            self.stream = None;
+        }
+        result
+    }
+}
+
+#[cfg(unix)]
+fn open(path: &Path) -> Result<Stream> {
+    UnixStream::connect(path)
+}
+
+#[cfg(windows)]
+fn open(path: &Path) -> Result<Stream> {
+    OpenOptions::new().write(true).open(path)
+}
+
+impl Write for SocketWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        let connected = self.is_connected();
+        match self.try_write_all(buf) {
+            // the connection broke since the last record (e.g. EPIPE), try a fresh one
+            Err(_) if connected => self.try_write_all(buf),
+            result => result,
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match &mut self.stream {
+            Some(stream) => stream.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Config;
+    use log::{Level, LevelFilter, Record};
+
+    #[cfg(unix)]
+    #[test]
+    fn socket_reconnect() {
+        use crate::WriteLogger;
+        use log::Log;
+        use std::io::{BufRead, BufReader};
+        use std::os::unix::net::UnixListener;
+
+        let path = std::env::temp_dir().join(format!("simplelog-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let logger = WriteLogger::new_socket(LevelFilter::Info, Config::minimal(), &path);
+        let log = |msg: &str| {
+            logger.log(
+                &Record::builder()
+                    .level(Level::Info)
+                    .args(format_args!("{}", msg))
+                    .build(),
+            )
+        };
+        let read = || {
+            let mut line = String::new();
+            BufReader::new(listener.accept().unwrap().0)
+                .read_line(&mut line)
+                .unwrap();
+            line
+        };
+
+        log("first");
+        // the collector restarts, dropping the connection
+        assert_eq!(read(), "first\n");
+        log("second");
+        assert_eq!(read(), "second\n");
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,66 @@
+//! Makes sure logging a record with the default config doesn't allocate.
+//!
+//! The paris formatter renders every message into a String first, so it is excluded.
+#![cfg(all(feature = "std", not(feature = "paris")))]
+
+use log::{Level, Log, Record};
+use simplelog::{Config, LevelFilter, WriteLogger};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn write_logger_does_not_allocate() {
+    let logger = WriteLogger::new(LevelFilter::Trace, Config::default(), io::sink());
+    let log = |level| {
+        logger.log(
+            &Record::builder()
+                .level(level)
+                .target("allocations")
+                .file(Some("tests/allocations.rs"))
+                .line(Some(42))
+                .args(format_args!("{} records", 1000))
+                .build(),
+        )
+    };
+
+    // the first record initializes the thread local buffers
+    log(Level::Trace);
+
+    let before = ALLOCATIONS.with(Cell::get);
+    for level in [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ] {
+        log(level);
+    }
+    assert_eq!(ALLOCATIONS.with(Cell::get), before);
+}
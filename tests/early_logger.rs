@@ -0,0 +1,65 @@
+//! Makes sure the EarlyLogger replays buffered records into the logger taking over,
+//! even if that logger logs records itself.
+#![cfg(feature = "std")]
+
+use log::{LevelFilter, Log, Metadata, Record};
+use simplelog::{CombinedLogger, Config, EarlyLogger, SharedLogger, SharedWriter, WriteLogger};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Logs a record through the global logger the first time it logs a record
+struct ReentrantLogger {
+    inner: Box<WriteLogger<SharedWriter<Vec<u8>>>>,
+    nested: AtomicBool,
+}
+
+impl Log for ReentrantLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        self.inner.log(record);
+        if !self.nested.swap(true, Ordering::SeqCst) {
+            log::warn!("nested");
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+impl SharedLogger for ReentrantLogger {
+    fn level(&self) -> LevelFilter {
+        self.inner.level()
+    }
+
+    fn config(&self) -> Option<&Config> {
+        self.inner.config()
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn Log> {
+        Box::new(*self)
+    }
+}
+
+#[test]
+fn early_logger_replays_buffered_records() {
+    EarlyLogger::init(LevelFilter::Debug).unwrap();
+    log::info!("first");
+    log::trace!("dropped");
+    log::debug!("second");
+
+    let output = SharedWriter::new(Vec::new());
+    CombinedLogger::init(vec![Box::new(ReentrantLogger {
+        inner: WriteLogger::new(LevelFilter::Trace, Config::minimal(), output.clone()),
+        nested: AtomicBool::new(false),
+    })])
+    .unwrap();
+    log::trace!("after");
+
+    assert_eq!(
+        String::from_utf8(output.lock().clone()).unwrap(),
+        "first\nnested\nsecond\nafter\n"
+    );
+}
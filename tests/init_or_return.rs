@@ -0,0 +1,39 @@
+//! Makes sure `CombinedLogger::init_or_return` hands the loggers back, if a logger is already set.
+#![cfg(feature = "std")]
+
+use log::{Level, Record};
+use simplelog::{CombinedLogger, Config, LevelFilter, WriteLogger};
+
+#[test]
+fn init_or_return_hands_back_loggers() {
+    assert!(CombinedLogger::init_or_return(vec![WriteLogger::new(
+        LevelFilter::Info,
+        Config::default(),
+        Vec::new()
+    )])
+    .is_ok());
+
+    let (_, loggers) = CombinedLogger::init_or_return(vec![WriteLogger::new(
+        LevelFilter::Info,
+        Config::minimal(),
+        Vec::new(),
+    )])
+    .expect_err("a logger was already set");
+    assert_eq!(loggers.len(), 1);
+
+    // the returned logger is still usable
+    let combined = CombinedLogger::new(loggers);
+    log::Log::log(
+        &*combined,
+        &Record::builder()
+            .level(Level::Info)
+            .args(format_args!("still here"))
+            .build(),
+    );
+    let output = combined.loggers()[0]
+        .as_any()
+        .downcast_ref::<WriteLogger<Vec<u8>>>()
+        .unwrap()
+        .with_writer(|w| String::from_utf8(w.clone()).unwrap());
+    assert_eq!(output, "still here\n");
+}
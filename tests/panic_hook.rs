@@ -0,0 +1,36 @@
+//! Makes sure `install_panic_hook` logs panics through the global logger.
+#![cfg(feature = "std")]
+
+use simplelog::{install_panic_hook, ConfigBuilder, LevelFilter, SharedWriter, WriteLogger};
+use std::thread;
+
+#[test]
+fn panic_hook_logs_panics() {
+    let output = SharedWriter::new(Vec::new());
+    WriteLogger::init(
+        LevelFilter::Error,
+        ConfigBuilder::new()
+            .set_time_level(LevelFilter::Off)
+            .set_thread_level(LevelFilter::Off)
+            .set_target_level(LevelFilter::Error)
+            .build(),
+        output.clone(),
+    )
+    .unwrap();
+    install_panic_hook();
+
+    let result = thread::Builder::new()
+        .name("worker".into())
+        .spawn(|| panic!("boom"))
+        .unwrap()
+        .join();
+    assert!(result.is_err());
+
+    let output = String::from_utf8(output.lock().clone()).unwrap();
+    // a backtrace may follow, if enabled in the environment
+    assert!(
+        output.starts_with("[ERROR] panic: thread 'worker' panicked at 'boom'"),
+        "{}",
+        output
+    );
+}
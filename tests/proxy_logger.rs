@@ -0,0 +1,35 @@
+//! Makes sure `ProxyLogger` forwards records to the logger currently set.
+#![cfg(feature = "std")]
+
+use log::{info, LevelFilter};
+use simplelog::{Config, ProxyLogger, SharedWriter, WriteLogger};
+
+#[test]
+fn proxy_forwards_to_current_logger() {
+    ProxyLogger::init().unwrap();
+    info!("discarded");
+
+    let first = SharedWriter::new(Vec::new());
+    let second = SharedWriter::new(Vec::new());
+    assert!(ProxyLogger::set(WriteLogger::new(
+        LevelFilter::Info,
+        Config::minimal(),
+        first.clone()
+    ))
+    .is_none());
+    info!("to first");
+
+    assert!(ProxyLogger::set(WriteLogger::new(
+        LevelFilter::Info,
+        Config::minimal(),
+        second.clone()
+    ))
+    .is_some());
+    info!("to second");
+
+    assert!(ProxyLogger::take().is_some());
+    info!("discarded");
+
+    assert_eq!(&*first.lock(), b"to first\n");
+    assert_eq!(&*second.lock(), b"to second\n");
+}
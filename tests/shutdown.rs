@@ -0,0 +1,28 @@
+//! Makes sure `shutdown` stops the background threads of the installed loggers.
+#![cfg(feature = "std")]
+
+use simplelog::{Config, HeartbeatLogger, Level, LevelFilter, SharedWriter, WriteLogger};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn shutdown_stops_heartbeats() {
+    let output = SharedWriter::new(Vec::new());
+    HeartbeatLogger::init(
+        Duration::from_millis(10),
+        Level::Info,
+        "alive",
+        WriteLogger::new(LevelFilter::Info, Config::minimal(), output.clone()),
+    )
+    .unwrap();
+    while !String::from_utf8_lossy(&output.lock()).contains("alive") {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    simplelog::shutdown();
+    // re-enable logging, the thread needs to be gone instead of just muted
+    log::set_max_level(LevelFilter::Info);
+    let len = output.lock().len();
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(output.lock().len(), len);
+}
@@ -0,0 +1,16 @@
+//! Makes sure the records of the global `TestLogger` are mirrored process-wide, including spawned threads.
+#![cfg(feature = "test")]
+
+use simplelog::{CapturedRecords, Config, LevelFilter, TestLogger};
+use std::thread;
+
+#[test]
+fn global_mirror_captures_spawned_threads() {
+    TestLogger::init_captured(LevelFilter::Info, Config::minimal()).unwrap();
+    thread::spawn(|| log::warn!("from a thread"))
+        .join()
+        .unwrap();
+    log::debug!("filtered");
+
+    assert_eq!(CapturedRecords::global().records(), ["from a thread"]);
+}
@@ -0,0 +1,10 @@
+//! Makes sure `try_init` tells an already initialized logger apart from installing one.
+#![cfg(feature = "std")]
+
+use simplelog::{Config, LevelFilter, SimpleLogger, WriteLogger};
+
+#[test]
+fn try_init_tolerates_existing_logger() {
+    assert!(SimpleLogger::try_init(LevelFilter::Info, Config::default()).unwrap());
+    assert!(!WriteLogger::try_init(LevelFilter::Info, Config::default(), Vec::new()).unwrap());
+}